@@ -76,10 +76,10 @@ struct UiState {
 }
 
 fn configure_visuals_system(mut contexts: EguiContexts) -> Result {
-    contexts.ctx_mut()?.set_visuals(egui::Visuals {
+    contexts.set_visuals(egui::Visuals {
         window_corner_radius: 0.0.into(),
         ..Default::default()
-    });
+    })?;
     Ok(())
 }
 
@@ -0,0 +1,124 @@
+use crate::EguiContext;
+use bevy_ecs::{
+    entity::Entity,
+    query::Added,
+    resource::Resource,
+    system::{Query, Res},
+};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// Controls the debug overlay that shows the pass number, FPS, and the repaint mode of each Egui context.
+///
+/// The overlay is drawn into the context itself (in the top right corner), after user UI.
+/// The repaint mode is "continuous" if Egui has requested a repaint during the pass (e.g. because of an animation),
+/// and "reactive" otherwise, which helps to diagnose why a UI is repainted every frame.
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_egui::debug_overlay::EguiDebugOverlay;
+/// fn toggle_debug_overlay_system(
+///     keys: Res<ButtonInput<KeyCode>>,
+///     mut debug_overlay: ResMut<EguiDebugOverlay>,
+/// ) {
+///     if keys.just_pressed(KeyCode::F12) {
+///         debug_overlay.enabled = !debug_overlay.enabled;
+///     }
+/// }
+/// ```
+#[derive(Resource, Clone, Debug, Default)]
+pub struct EguiDebugOverlay {
+    /// Whether the overlay is shown, disabled by default.
+    pub enabled: bool,
+    /// Whether the outlines of clip rectangles are drawn, disabled by default.
+    ///
+    /// Every distinct clip rectangle of the Egui output is outlined in red on top of the UI,
+    /// which helps to find out why content gets unexpectedly clipped (e.g. by a panel).
+    /// Is independent from [`EguiDebugOverlay::enabled`].
+    pub show_clip_rects: bool,
+}
+
+/// Shares the [`EguiDebugOverlay::enabled`] flag with the end pass callbacks of Egui contexts.
+#[derive(Resource, Default)]
+pub struct EguiDebugOverlayFlag {
+    enabled: Arc<AtomicBool>,
+}
+
+/// Copies [`EguiDebugOverlay::enabled`] to [`EguiDebugOverlayFlag`].
+pub fn update_debug_overlay_flag_system(
+    debug_overlay: Res<EguiDebugOverlay>,
+    debug_overlay_flag: Res<EguiDebugOverlayFlag>,
+) {
+    debug_overlay_flag
+        .enabled
+        .store(debug_overlay.enabled, Ordering::Relaxed);
+}
+
+/// Returns the shapes with outlines of their distinct clip rectangles appended, see [`EguiDebugOverlay::show_clip_rects`].
+pub(crate) fn with_clip_rect_outlines(
+    mut shapes: Vec<egui::epaint::ClippedShape>,
+) -> Vec<egui::epaint::ClippedShape> {
+    let mut clip_rects = Vec::<egui::Rect>::new();
+    for shape in shapes.iter() {
+        // Shapes that aren't clipped have infinite clip rectangles.
+        if shape.clip_rect.is_finite() && !clip_rects.contains(&shape.clip_rect) {
+            clip_rects.push(shape.clip_rect);
+        }
+    }
+    shapes.extend(
+        clip_rects
+            .into_iter()
+            .map(|clip_rect| egui::epaint::ClippedShape {
+                clip_rect: egui::Rect::EVERYTHING,
+                shape: egui::Shape::rect_stroke(
+                    clip_rect,
+                    0.0,
+                    egui::Stroke::new(1.0, egui::Color32::RED),
+                    egui::StrokeKind::Inside,
+                ),
+            }),
+    );
+    shapes
+}
+
+/// Installs end pass callbacks that draw the debug overlay for new contexts.
+pub fn setup_debug_overlay_callbacks_system(
+    debug_overlay_flag: Res<EguiDebugOverlayFlag>,
+    mut contexts: Query<(Entity, &mut EguiContext), Added<EguiContext>>,
+) {
+    for (entity, mut context) in contexts.iter_mut() {
+        let enabled = debug_overlay_flag.enabled.clone();
+        // End pass callbacks run after all the user UI of the pass, so the overlay is drawn on top of it.
+        context.get_mut().on_end_pass(
+            "bevy_egui_debug_overlay",
+            Arc::new(move |ctx| {
+                if !enabled.load(Ordering::Relaxed) {
+                    return;
+                }
+                let pass_nr = ctx.cumulative_pass_nr();
+                let stable_dt = ctx.input(|input| input.stable_dt);
+                let repaint_mode = if ctx.has_requested_repaint() {
+                    "continuous"
+                } else {
+                    "reactive"
+                };
+                egui::Area::new(egui::Id::new("bevy_egui_debug_overlay").with(entity))
+                    .order(egui::Order::Debug)
+                    .anchor(egui::Align2::RIGHT_TOP, [-4.0, 4.0])
+                    .interactable(false)
+                    .show(ctx, |ui| {
+                        egui::Frame::popup(ui.style()).show(ui, |ui| {
+                            ui.label(format!("Context: {entity}"));
+                            ui.label(format!("Pass: {pass_nr}"));
+                            if stable_dt > 0.0 {
+                                ui.label(format!("FPS: {:.0}", 1.0 / stable_dt));
+                            }
+                            ui.label(format!("Repaint: {repaint_mode}"));
+                        });
+                    });
+            }),
+        );
+    }
+}
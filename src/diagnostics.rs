@@ -0,0 +1,111 @@
+use crate::EguiContext;
+use bevy_diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, DiagnosticsStore};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    prelude::RemovedComponents,
+    query::{With, Without},
+    system::{Commands, Query, ResMut},
+};
+
+/// Paths of the diagnostics measured for an Egui context, is inserted by [`register_egui_diagnostics_system`].
+///
+/// The paths have the `egui/<context entity>/` prefix and the following names:
+/// - `areas`: the number of areas (windows, popups, tooltips, etc.) Egui remembers,
+/// - `retained_ids`: the number of entries in [`egui::Memory::data`] (widget states, etc.),
+/// - `font_atlas_size`: the size of the font atlas in texels,
+/// - `texture_memory`: the memory used by all Egui-managed textures in KiB.
+///
+/// The diagnostics are shown by [`bevy_diagnostic::LogDiagnosticsPlugin`] and other tools reading
+/// [`DiagnosticsStore`], which can help to find leaks (e.g. widget ids that are never reused).
+#[derive(Component, Clone, Debug)]
+pub struct EguiContextDiagnosticPaths {
+    /// The number of areas.
+    pub areas: DiagnosticPath,
+    /// The number of retained ids.
+    pub retained_ids: DiagnosticPath,
+    /// The font atlas size in texels.
+    pub font_atlas_size: DiagnosticPath,
+    /// The memory used by textures in KiB.
+    pub texture_memory: DiagnosticPath,
+}
+
+impl EguiContextDiagnosticPaths {
+    /// Creates the paths for a context.
+    pub fn new(context: Entity) -> Self {
+        let path = |name: &str| DiagnosticPath::new(format!("egui/{context}/{name}"));
+        Self {
+            areas: path("areas"),
+            retained_ids: path("retained_ids"),
+            font_atlas_size: path("font_atlas_size"),
+            texture_memory: path("texture_memory"),
+        }
+    }
+}
+
+/// Registers diagnostics for contexts that don't have them yet (including the ones created before
+/// [`DiagnosticsStore`] was inserted), disables the diagnostics of removed contexts.
+pub fn register_egui_diagnostics_system(
+    mut commands: Commands,
+    mut diagnostics_store: ResMut<DiagnosticsStore>,
+    mut removed_contexts: RemovedComponents<EguiContext>,
+    contexts: Query<Entity, (With<EguiContext>, Without<EguiContextDiagnosticPaths>)>,
+) {
+    // `DiagnosticsStore` doesn't support removing diagnostics, so they are disabled instead.
+    for context in removed_contexts.read() {
+        let paths = EguiContextDiagnosticPaths::new(context);
+        for path in [
+            &paths.areas,
+            &paths.retained_ids,
+            &paths.font_atlas_size,
+            &paths.texture_memory,
+        ] {
+            if let Some(diagnostic) = diagnostics_store.get_mut(path) {
+                diagnostic.is_enabled = false;
+                diagnostic.clear_history();
+            }
+        }
+        if let Ok(mut entity) = commands.get_entity(context) {
+            entity.remove::<EguiContextDiagnosticPaths>();
+        }
+    }
+
+    for context in contexts.iter() {
+        let paths = EguiContextDiagnosticPaths::new(context);
+        diagnostics_store.add(Diagnostic::new(paths.areas.clone()));
+        diagnostics_store.add(Diagnostic::new(paths.retained_ids.clone()));
+        diagnostics_store
+            .add(Diagnostic::new(paths.font_atlas_size.clone()).with_suffix(" texels"));
+        diagnostics_store.add(Diagnostic::new(paths.texture_memory.clone()).with_suffix(" KiB"));
+        commands.entity(context).insert(paths);
+    }
+}
+
+/// Measures memory usage of contexts.
+pub fn measure_egui_diagnostics_system(
+    mut diagnostics: Diagnostics,
+    mut contexts: Query<(&mut EguiContext, &EguiContextDiagnosticPaths)>,
+) {
+    for (mut context, paths) in contexts.iter_mut() {
+        let ctx = context.get_mut();
+        diagnostics.add_measurement(&paths.areas, || {
+            ctx.memory(|memory| memory.areas().count()) as f64
+        });
+        diagnostics.add_measurement(&paths.retained_ids, || {
+            ctx.memory(|memory| memory.data.len()) as f64
+        });
+        diagnostics.add_measurement(&paths.font_atlas_size, || {
+            let [width, height] = ctx.fonts(|fonts| fonts.font_image_size());
+            (width * height) as f64
+        });
+        diagnostics.add_measurement(&paths.texture_memory, || {
+            let bytes = ctx
+                .tex_manager()
+                .read()
+                .allocated()
+                .map(|(_, meta)| meta.bytes_used())
+                .sum::<usize>();
+            bytes as f64 / 1024.0
+        });
+    }
+}
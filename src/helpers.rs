@@ -3,7 +3,10 @@ use bevy_ecs::{
     query::{QueryData, QueryEntityError, QueryFilter, QueryItem, ROQueryItem},
     system::Query,
 };
-use bevy_input::keyboard::{Key, KeyCode};
+use bevy_input::{
+    keyboard::{Key, KeyCode},
+    mouse::MouseButton,
+};
 
 /// Translates [`egui::CursorIcon`] into [`bevy_window::SystemCursorIcon`].
 #[inline(always)]
@@ -49,6 +52,19 @@ pub fn egui_to_winit_cursor_icon(
     }
 }
 
+/// Translates [`MouseButton`] into [`egui::PointerButton`].
+#[inline(always)]
+pub fn bevy_to_egui_pointer_button(button: MouseButton) -> Option<egui::PointerButton> {
+    match button {
+        MouseButton::Left => Some(egui::PointerButton::Primary),
+        MouseButton::Right => Some(egui::PointerButton::Secondary),
+        MouseButton::Middle => Some(egui::PointerButton::Middle),
+        MouseButton::Back => Some(egui::PointerButton::Extra1),
+        MouseButton::Forward => Some(egui::PointerButton::Extra2),
+        MouseButton::Other(_) => None,
+    }
+}
+
 /// Matches the implementation of <https://github.com/emilk/egui/blob/68b3ef7f6badfe893d3bbb1f791b481069d807d9/crates/egui-winit/src/lib.rs#L1005>.
 #[inline(always)]
 pub fn bevy_to_egui_key(key: &Key) -> Option<egui::Key> {
@@ -91,6 +107,25 @@ pub fn bevy_to_egui_key(key: &Key) -> Option<egui::Key> {
         Key::F18 => egui::Key::F18,
         Key::F19 => egui::Key::F19,
         Key::F20 => egui::Key::F20,
+        Key::F21 => egui::Key::F21,
+        Key::F22 => egui::Key::F22,
+        Key::F23 => egui::Key::F23,
+        Key::F24 => egui::Key::F24,
+        Key::F25 => egui::Key::F25,
+        Key::F26 => egui::Key::F26,
+        Key::F27 => egui::Key::F27,
+        Key::F28 => egui::Key::F28,
+        Key::F29 => egui::Key::F29,
+        Key::F30 => egui::Key::F30,
+        Key::F31 => egui::Key::F31,
+        Key::F32 => egui::Key::F32,
+        Key::F33 => egui::Key::F33,
+        Key::F34 => egui::Key::F34,
+        Key::F35 => egui::Key::F35,
+        Key::Cut => egui::Key::Cut,
+        Key::Copy => egui::Key::Copy,
+        Key::Paste => egui::Key::Paste,
+        Key::BrowserBack => egui::Key::BrowserBack,
 
         _ => return None,
     };
@@ -120,10 +155,11 @@ pub fn bevy_to_egui_physical_key(key: &KeyCode) -> Option<egui::Key> {
 
         // Punctuation
         KeyCode::Space => egui::Key::Space,
-        KeyCode::Comma => egui::Key::Comma,
-        KeyCode::Period => egui::Key::Period,
+        KeyCode::Comma | KeyCode::NumpadComma => egui::Key::Comma,
+        KeyCode::Period | KeyCode::NumpadDecimal => egui::Key::Period,
         // KeyCode::Colon => egui::Key::Colon, // NOTE: there is no physical colon key on an american keyboard
         KeyCode::Semicolon => egui::Key::Semicolon,
+        KeyCode::Quote => egui::Key::Quote,
         KeyCode::Backslash => egui::Key::Backslash,
         KeyCode::Slash | KeyCode::NumpadDivide => egui::Key::Slash,
         KeyCode::BracketLeft => egui::Key::OpenBracket,
@@ -135,7 +171,7 @@ pub fn bevy_to_egui_physical_key(key: &KeyCode) -> Option<egui::Key> {
         KeyCode::Paste => egui::Key::Paste,
         KeyCode::Minus | KeyCode::NumpadSubtract => egui::Key::Minus,
         KeyCode::NumpadAdd => egui::Key::Plus,
-        KeyCode::Equal => egui::Key::Equals,
+        KeyCode::Equal | KeyCode::NumpadEqual => egui::Key::Equals,
 
         KeyCode::Digit0 | KeyCode::Numpad0 => egui::Key::Num0,
         KeyCode::Digit1 | KeyCode::Numpad1 => egui::Key::Num1,
@@ -195,6 +231,23 @@ pub fn bevy_to_egui_physical_key(key: &KeyCode) -> Option<egui::Key> {
         KeyCode::F18 => egui::Key::F18,
         KeyCode::F19 => egui::Key::F19,
         KeyCode::F20 => egui::Key::F20,
+        KeyCode::F21 => egui::Key::F21,
+        KeyCode::F22 => egui::Key::F22,
+        KeyCode::F23 => egui::Key::F23,
+        KeyCode::F24 => egui::Key::F24,
+        KeyCode::F25 => egui::Key::F25,
+        KeyCode::F26 => egui::Key::F26,
+        KeyCode::F27 => egui::Key::F27,
+        KeyCode::F28 => egui::Key::F28,
+        KeyCode::F29 => egui::Key::F29,
+        KeyCode::F30 => egui::Key::F30,
+        KeyCode::F31 => egui::Key::F31,
+        KeyCode::F32 => egui::Key::F32,
+        KeyCode::F33 => egui::Key::F33,
+        KeyCode::F34 => egui::Key::F34,
+        KeyCode::F35 => egui::Key::F35,
+
+        KeyCode::BrowserBack => egui::Key::BrowserBack,
         _ => return None,
     };
     Some(key)
@@ -242,11 +295,40 @@ pub fn egui_rect_into_rect(rect: egui::Rect) -> bevy_math::Rect {
     }
 }
 
-pub(crate) trait QueryHelper<'s> {
+/// Extends [`Query`] with getters that return [`Option`] instead of [`Result`].
+///
+/// Is useful when an entity is expected to be missing from a query as a part of the normal flow
+/// (for example, when not every camera has an Egui context), so that the lookup doesn't need
+/// to be treated as an error.
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_egui::{egui, helpers::QueryHelper, EguiContext};
+/// fn ui_system(mut contexts: Query<&mut EguiContext>, cameras: Query<Entity, With<Camera>>) {
+///     for camera in &cameras {
+///         let Some(mut ctx) = contexts.get_some_mut(camera) else {
+///             continue;
+///         };
+///         egui::Window::new("Hello").show(ctx.get_mut(), |ui| {
+///             ui.label("world");
+///         });
+///     }
+/// }
+/// ```
+pub trait QueryHelper<'s> {
+    /// Data fetched by the query.
     type QueryData: bevy_ecs::query::QueryData;
 
+    /// Returns the read-only query item for the given entity.
+    ///
+    /// Returns [`None`] if the entity doesn't exist or doesn't match the query
+    /// (e.g. if it doesn't have one of the queried components).
     fn get_some(&self, entity: Entity) -> Option<ROQueryItem<'_, 's, Self::QueryData>>;
 
+    /// Returns the query item for the given entity.
+    ///
+    /// Returns [`None`] if the entity doesn't exist or doesn't match the query
+    /// (e.g. if it doesn't have one of the queried components).
     fn get_some_mut(&mut self, entity: Entity) -> Option<QueryItem<'_, 's, Self::QueryData>>;
 }
 
@@ -256,7 +338,9 @@ impl<'s, D: QueryData, F: QueryFilter> QueryHelper<'s> for Query<'_, 's, D, F> {
     fn get_some(&self, entity: Entity) -> Option<ROQueryItem<'_, 's, Self::QueryData>> {
         match self.get(entity) {
             Ok(item) => Some(item),
-            Err(QueryEntityError::EntityDoesNotExist(_)) => None,
+            Err(
+                QueryEntityError::EntityDoesNotExist(_) | QueryEntityError::QueryDoesNotMatch(..),
+            ) => None,
             err => {
                 err.unwrap();
                 unreachable!()
@@ -267,7 +351,9 @@ impl<'s, D: QueryData, F: QueryFilter> QueryHelper<'s> for Query<'_, 's, D, F> {
     fn get_some_mut(&mut self, entity: Entity) -> Option<QueryItem<'_, 's, Self::QueryData>> {
         match self.get_mut(entity) {
             Ok(item) => Some(item),
-            Err(QueryEntityError::EntityDoesNotExist(_)) => None,
+            Err(
+                QueryEntityError::EntityDoesNotExist(_) | QueryEntityError::QueryDoesNotMatch(..),
+            ) => None,
             err => {
                 err.unwrap();
                 unreachable!()
@@ -275,3 +361,54 @@ impl<'s, D: QueryData, F: QueryFilter> QueryHelper<'s> for Query<'_, 's, D, F> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::QueryHelper;
+    use bevy_ecs::{
+        component::Component,
+        system::{Query, SystemState},
+        world::World,
+    };
+
+    #[derive(Component, Debug, PartialEq)]
+    struct TestComponent(u32);
+
+    #[test]
+    fn test_get_some_existing_entity() {
+        let mut world = World::new();
+        let entity = world.spawn(TestComponent(1)).id();
+
+        let mut state = SystemState::<Query<&mut TestComponent>>::new(&mut world);
+        let mut query = state.get_mut(&mut world);
+
+        assert_eq!(query.get_some(entity), Some(&TestComponent(1)));
+        query.get_some_mut(entity).unwrap().0 = 2;
+        assert_eq!(query.get_some(entity), Some(&TestComponent(2)));
+    }
+
+    #[test]
+    fn test_get_some_missing_entity() {
+        let mut world = World::new();
+        let entity = world.spawn(TestComponent(1)).id();
+        world.despawn(entity);
+
+        let mut state = SystemState::<Query<&mut TestComponent>>::new(&mut world);
+        let mut query = state.get_mut(&mut world);
+
+        assert!(query.get_some(entity).is_none());
+        assert!(query.get_some_mut(entity).is_none());
+    }
+
+    #[test]
+    fn test_get_some_missing_component() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+
+        let mut state = SystemState::<Query<&mut TestComponent>>::new(&mut world);
+        let mut query = state.get_mut(&mut world);
+
+        assert!(query.get_some(entity).is_none());
+        assert!(query.get_some_mut(entity).is_none());
+    }
+}
@@ -2,19 +2,31 @@
 use crate::text_agent::{is_mobile_safari, update_text_agent};
 use crate::{
     helpers::{vec2_into_egui_pos2, QueryHelper},
-    EguiContext, EguiContextSettings, EguiGlobalSettings, EguiInput, EguiOutput,
+    EguiContext, EguiContextClipboard, EguiContextComputedScaleFactor, EguiContextSettings,
+    EguiEscapeBehavior, EguiGlobalSettings, EguiInput, EguiOutput,
+};
+use bevy_ecs::{
+    event::{EventId, EventIteratorWithId},
+    prelude::*,
+    query::QueryEntityError,
+    system::SystemParam,
 };
-use bevy_ecs::{event::EventIterator, prelude::*, system::SystemParam};
 use bevy_input::{
+    gamepad::{Gamepad, GamepadAxisChangedEvent, GamepadButtonChangedEvent},
+    gestures::PinchGesture,
     keyboard::{Key, KeyCode, KeyboardFocusLost, KeyboardInput},
-    mouse::{MouseButton, MouseButtonInput, MouseScrollUnit, MouseWheel},
-    touch::TouchInput,
+    mouse::{MouseButton, MouseButtonInput, MouseMotion, MouseScrollUnit, MouseWheel},
+    touch::{TouchInput, Touches},
     ButtonInput, ButtonState,
 };
 use bevy_log::{self as log};
 use bevy_time::{Real, Time};
-use bevy_window::{CursorMoved, FileDragAndDrop, Ime, Window};
+use bevy_window::{
+    CursorGrabMode, CursorLeft, CursorMoved, CursorOptions, FileDragAndDrop, Ime, PrimaryWindow,
+    Window,
+};
 use egui::Modifiers;
+use std::sync::{Arc, Mutex};
 
 /// Cached pointer position, used to populate [`egui::Event::PointerButton`] events.
 #[derive(Component, Default)]
@@ -23,11 +35,83 @@ pub struct EguiContextPointerPosition {
     pub position: egui::Pos2,
 }
 
+/// Stores the last mouse cursor position of a window context, used to compute [`egui::Event::MouseMoved`] deltas
+/// (see [`crate::EguiContextSettings::send_mouse_moved_events`]).
+///
+/// Unlike [`EguiContextPointerPosition`], it's not affected by touches and is reset when the cursor leaves the window,
+/// so that the first movement after that doesn't produce a jump.
+#[derive(Component, Default, Debug, Clone, Copy, PartialEq)]
+pub struct EguiContextMousePosition {
+    /// Last mouse position, `None` if unknown.
+    pub last_position: Option<egui::Pos2>,
+}
+
+/// Stores all pointer positions received during the current frame, in the order they were received.
+///
+/// Bevy can deliver multiple [`CursorMoved`] events per frame, and while each of them is forwarded to Egui,
+/// [`EguiContextPointerPosition`] stores only the last one. Insert this component into an [`EguiContext`] entity
+/// if you need the intermediate positions as well (e.g. for smoothing freehand drawing).
+///
+/// The component is cleared and populated by [`write_window_pointer_moved_events_system`].
+#[derive(Component, Clone, Debug, Default)]
+pub struct EguiContextPointerTrail(pub Vec<egui::Pos2>);
+
+/// Counts input events received by an Egui context during the current frame, can help with debugging lost input.
+///
+/// Insert this component into an [`EguiContext`] entity to enable counting.
+/// The counters are reset and updated by [`write_egui_input_system`].
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EguiInputStats {
+    /// The number of [`EguiInputEvent`] and [`EguiFileDragAndDropEvent`] events that were written to [`EguiInput`].
+    pub events_this_frame: usize,
+    /// The number of events that were sent to this entity but were dropped, as the entity isn't a valid Egui context
+    /// (i.e. it lacks [`EguiInput`] or [`EguiContextPointerPosition`]).
+    ///
+    /// Such events are logged as errors, and this counter makes them visible for entities that have the component
+    /// but aren't (or are no longer) contexts, e.g. a camera that gets its [`EguiContext`] later.
+    pub dropped_for_missing_context: usize,
+}
+
 /// Stores an active touch id.
 #[derive(Component, Default)]
 pub struct EguiContextPointerTouchId {
     /// Active touch id.
     pub pointer_touch_id: Option<u64>,
+    /// Ids of all the touches that are currently active.
+    ///
+    /// Mouse emulation is paused while there's more than one active touch, so that Egui's
+    /// multi-touch gestures (e.g. two-finger scrolling or zooming) don't fight the emulated pointer.
+    pub active_touch_ids: bevy_platform::collections::HashSet<u64>,
+    /// The last position (and the time it was received at) of the touch that emulates the pointer,
+    /// is used for [`EguiContextSettings::pointer_latency_compensation`] and [`EguiContextSettings::touch_inertia`].
+    pub last_pointer_touch_sample: Option<(egui::Pos2, f64)>,
+    /// Velocity (in points per second) of the touch that emulates the pointer,
+    /// is used for [`EguiContextSettings::pointer_latency_compensation`] and [`EguiContextSettings::touch_inertia`].
+    pub pointer_touch_velocity: egui::Vec2,
+    /// Velocity (in points per second) of the scroll momentum that is synthesized after the pointer touch ends,
+    /// see [`EguiContextSettings::touch_inertia`].
+    pub inertia_velocity: Option<egui::Vec2>,
+}
+
+/// Stores the key that is currently held, is used for synthesizing repeat events
+/// (see [`EguiContextSettings::key_repeat`]).
+#[derive(Component, Default)]
+pub struct EguiContextKeyRepeatState {
+    /// The last pressed key, if it's still held.
+    pub held_key: Option<HeldKey>,
+}
+
+/// A held key, see [`EguiContextKeyRepeatState`].
+#[derive(Clone, Debug)]
+pub struct HeldKey {
+    /// Logical key.
+    pub key: egui::Key,
+    /// Physical key.
+    pub physical_key: Option<egui::Key>,
+    /// Text that is input with the key, is repeated as well.
+    pub text: Option<String>,
+    /// Time left until the next repeat event (in seconds).
+    pub until_next_repeat: f32,
 }
 
 /// Indicates whether [IME](https://en.wikipedia.org/wiki/Input_method) is enabled or disabled to avoid sending event duplicates.
@@ -48,6 +132,28 @@ pub struct EguiInputEvent {
     pub event: egui::Event,
 }
 
+/// Is sent by [`write_keyboard_input_events_system`] for keyboard input that can't be passed to Egui
+/// as [`egui::Event::Key`], as neither the logical nor the physical key has an [`egui::Key`] counterpart
+/// (e.g. media keys, or modifier-only presses).
+///
+/// Unlike [`KeyboardInput`], the event is addressed to a context, which is handy for handling such keys
+/// in in-game menus.
+#[derive(Event, BufferedEvent, Clone, Debug)]
+pub struct EguiUnmappedKeyEvent {
+    /// The context that would have received the key event.
+    pub context: Entity,
+    /// Physical key.
+    pub key_code: KeyCode,
+    /// Logical key.
+    pub logical_key: Key,
+    /// Whether the key is pressed or released.
+    pub state: ButtonState,
+    /// Whether the event is an OS key repeat.
+    pub repeat: bool,
+    /// Modifier keys state.
+    pub modifiers: Modifiers,
+}
+
 #[derive(Event, BufferedEvent)]
 /// Wraps [`bevy::FileDragAndDrop`](bevy_window::FileDragAndDrop) events emitted by [`crate::EguiInputSet`] systems.
 pub struct EguiFileDragAndDropEvent {
@@ -57,6 +163,38 @@ pub struct EguiFileDragAndDropEvent {
     pub event: FileDragAndDrop,
 }
 
+/// Insert this resource to transform dropped files before they reach Egui.
+///
+/// By default, [`write_egui_input_system`] passes [`egui::DroppedFile`] with only the `path` field set.
+/// This hook can be used to replace it with `bytes`, `name` or `mime` (for example, when files
+/// need to be copied into a virtual file system first).
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_egui::input::EguiDroppedFileTransform;
+///
+/// fn setup_system(mut commands: Commands) {
+///     commands.insert_resource(EguiDroppedFileTransform::new(|mut file| {
+///         if let Some(path) = file.path.take() {
+///             file.name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+///             file.bytes = std::fs::read(&path).ok().map(Into::into);
+///         }
+///         file
+///     }));
+/// }
+/// ```
+#[derive(Resource)]
+pub struct EguiDroppedFileTransform(
+    pub Box<dyn Fn(egui::DroppedFile) -> egui::DroppedFile + Send + Sync>,
+);
+
+impl EguiDroppedFileTransform {
+    /// Constructs the resource from a closure.
+    pub fn new(f: impl Fn(egui::DroppedFile) -> egui::DroppedFile + Send + Sync + 'static) -> Self {
+        Self(Box::new(f))
+    }
+}
+
 #[derive(Resource, Clone)]
 /// Insert this resource when a pointer hovers over a non-window (e.g. world-space) [`EguiContext`] entity.
 /// Also, make sure to update an [`EguiContextPointerPosition`] component of a hovered entity.
@@ -72,15 +210,23 @@ pub struct HoveredNonWindowEguiContext(pub Entity);
 /// resource will get inserted on mouse button press or touch start event
 /// (and removed if no hovered non-window context exists respectively).
 ///
-/// Atm, it's up to users to update [`HoveredNonWindowEguiContext`] and [`EguiContextPointerPosition`].
-/// We might be able to add proper `bevy_picking` support for world space UI once [`bevy_picking::backend::HitData`]
-/// starts exposing triangle index or UV.
+/// With the `picking` feature enabled, [`HoveredNonWindowEguiContext`] and [`EguiContextPointerPosition`]
+/// are updated automatically for entities set up with `BevyEguiEntityCommandsExt::add_picking_observers_for_context`
+/// (the pointer position is computed from the UV coordinates of a picked mesh).
+/// Otherwise, it's up to users to update them.
 ///
 /// Updating focused contexts happens during [`crate::EguiInputSet::FocusContext`],
 /// see [`write_pointer_button_events_system`] and [`write_window_touch_events_system`].
 #[derive(Resource, Clone)]
 pub struct FocusedNonWindowEguiContext(pub Entity);
 
+/// Lists non-window contexts that can be focused with [`EguiGlobalSettings::focus_cycle_shortcut`],
+/// in the order in which the shortcut cycles through them.
+///
+/// The list is maintained by users: despawned contexts are skipped, but not removed.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct EguiFocusCycleContexts(pub Vec<Entity>);
+
 /// Stores "pressed" state of modifier keys.
 #[derive(Resource, Clone, Copy, Debug)]
 pub struct ModifierKeysState {
@@ -203,11 +349,55 @@ impl WindowToEguiContextMap {
             window_contexts.remove(&egui_context_entity);
         }
     }
+
+    /// Removes closed windows from the map, sends [`EguiContextWindowClosedEvent`] for their contexts,
+    /// and despawns the contexts if [`EguiGlobalSettings::despawn_contexts_on_window_close`] is enabled.
+    pub fn on_window_removed_system(
+        mut commands: Commands,
+        mut res: ResMut<Self>,
+        mut removed_windows: RemovedComponents<Window>,
+        mut window_closed_event_writer: EventWriter<EguiContextWindowClosedEvent>,
+        egui_global_settings: Res<EguiGlobalSettings>,
+    ) {
+        for window_entity in removed_windows.read() {
+            let Some(window_contexts) = res.window_to_contexts.remove(&window_entity) else {
+                continue;
+            };
+
+            for egui_context_entity in window_contexts {
+                res.context_to_window.remove(&egui_context_entity);
+                window_closed_event_writer.write(EguiContextWindowClosedEvent {
+                    context: egui_context_entity,
+                    window: window_entity,
+                });
+                if egui_global_settings.despawn_contexts_on_window_close {
+                    if let Ok(mut entity_commands) = commands.get_entity(egui_context_entity) {
+                        entity_commands.despawn();
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Is sent when the window that an Egui context renders to is closed (i.e. its [`Window`] component is removed).
+///
+/// Once the window is closed, the context (a camera targeting the window) stays around, but doesn't receive input
+/// and isn't rendered anymore. React to this event to clean up the related state, or enable
+/// [`EguiGlobalSettings::despawn_contexts_on_window_close`] to despawn such contexts automatically.
+/// If the primary context is closed, [`crate::EguiPrimaryContextPass`] isn't run until a new primary context is created.
+#[derive(Event, BufferedEvent, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EguiContextWindowClosedEvent {
+    /// The context entity.
+    pub context: Entity,
+    /// The closed window entity.
+    pub window: Entity,
 }
 
 /// Iterates over pairs of `(Event, Entity)`, where the entity points to the context that the event is related to.
 pub struct EguiContextsEventIterator<'a, E: BufferedEvent, F> {
-    event_iter: EventIterator<'a, E>,
+    event_iter: EventIteratorWithId<'a, E>,
+    resent_event_ids: std::ops::Range<usize>,
     map_event_to_window_id_f: F,
     current_event: Option<&'a E>,
     current_event_contexts: Vec<Entity>,
@@ -226,7 +416,11 @@ impl<'a, E: BufferedEvent, F: FnMut(&'a E) -> Entity> Iterator
         }
 
         if self.current_event.is_none() {
-            self.current_event = self.event_iter.next();
+            // Events re-sent by `absorb_bevy_input_system` have already been read.
+            self.current_event = self
+                .event_iter
+                .find(|(_, id)| !self.resent_event_ids.contains(&id.id))
+                .map(|(event, _)| event);
 
             if self.non_window_context.is_some() {
                 return self.current_event.zip(self.non_window_context);
@@ -247,10 +441,110 @@ impl<'a, E: BufferedEvent, F: FnMut(&'a E) -> Entity> Iterator
     }
 }
 
+#[derive(SystemParam)]
+/// A system param for pushing Egui events to contexts, the recommended way for plugins to integrate
+/// custom input sources (e.g. input devices that Bevy doesn't support).
+///
+/// The events are wrapped into [`EguiInputEvent`] and fed to Egui by [`write_egui_input_system`]
+/// in [`crate::EguiInputSet::WriteEguiEvents`]. To get the events into the pass of the same frame,
+/// schedule the system in [`crate::EguiInputSet::ReadBevyEvents`]:
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_egui::{egui, input::EguiInputWriter, EguiInputSet, EguiPreUpdateSet, PrimaryEguiContext};
+/// # let mut app = App::new();
+/// app.add_systems(
+///     PreUpdate,
+///     push_custom_events_system
+///         .in_set(EguiPreUpdateSet::ProcessInput)
+///         .in_set(EguiInputSet::ReadBevyEvents),
+/// );
+///
+/// fn push_custom_events_system(
+///     primary_context: Single<Entity, With<PrimaryEguiContext>>,
+///     mut egui_input_writer: EguiInputWriter,
+/// ) {
+///     egui_input_writer.push(*primary_context, egui::Event::Text("a".to_string()));
+/// }
+/// ```
+///
+/// Events pushed after [`crate::EguiInputSet::WriteEguiEvents`] (e.g. in [`bevy_app::Update`]) aren't lost,
+/// they are fed to Egui in the next frame.
+pub struct EguiInputWriter<'w, 's> {
+    egui_input_event_writer: EventWriter<'w, EguiInputEvent>,
+    wants_input: Query<'w, 's, &'static EguiWantsInput>,
+}
+
+impl EguiInputWriter<'_, '_> {
+    /// Pushes an event to a context.
+    pub fn push(&mut self, context: Entity, event: egui::Event) {
+        self.egui_input_event_writer
+            .write(EguiInputEvent { context, event });
+    }
+
+    /// Pushes several events to a context, preserving their order.
+    pub fn push_batch(&mut self, context: Entity, events: impl IntoIterator<Item = egui::Event>) {
+        self.egui_input_event_writer.write_batch(
+            events
+                .into_iter()
+                .map(|event| EguiInputEvent { context, event }),
+        );
+    }
+
+    /// Pushes [`egui::Event::Paste`] to a context.
+    ///
+    /// Can be used for implementing custom paste menus or inserting text from sources other than the clipboard.
+    /// The text is pasted into the focused widget (e.g. [`egui::TextEdit`]) during the next pass of the context
+    /// (or the pass of the next frame, if called after [`crate::EguiInputSet::WriteEguiEvents`]).
+    /// Does nothing (but logs a warning) if the context doesn't want keyboard input, i.e. nothing is focused.
+    pub fn paste_text(&mut self, context: Entity, text: &str) -> Result<(), QueryEntityError> {
+        if !self.wants_input.get(context)?.wants_keyboard_input() {
+            log::warn!("Ignoring pasted text: Egui context {context} doesn't have keyboard focus");
+            return Ok(());
+        }
+        self.push(context, egui::Event::Paste(text.to_owned()));
+        Ok(())
+    }
+
+    /// Pushes [`egui::Event::Copy`] to a context.
+    ///
+    /// Can be used for implementing "Copy" toolbar buttons, which is especially useful on web, where
+    /// copying is otherwise triggered only by the browser `copy` event (i.e. keyboard shortcuts or the browser menu).
+    /// The selected text of the focused widget is copied during the next pass of the context,
+    /// and the resulting [`egui::OutputCommand::CopyText`] is written to [`crate::EguiClipboard`] (or [`EguiContextClipboard`]).
+    /// Egui ignores the event if there's no focused widget with selected text.
+    ///
+    /// See [`EguiInputWriter::paste_text`] for pasting.
+    pub fn trigger_copy(&mut self, context: Entity) -> Result<(), QueryEntityError> {
+        self.push_clipboard_event(context, egui::Event::Copy)
+    }
+
+    /// Pushes [`egui::Event::Cut`] to a context.
+    ///
+    /// Works the same way as [`EguiInputWriter::trigger_copy`], but also removes the selected text.
+    pub fn trigger_cut(&mut self, context: Entity) -> Result<(), QueryEntityError> {
+        self.push_clipboard_event(context, egui::Event::Cut)
+    }
+
+    fn push_clipboard_event(
+        &mut self,
+        context: Entity,
+        event: egui::Event,
+    ) -> Result<(), QueryEntityError> {
+        // `EguiWantsInput::wants_keyboard_input` isn't checked, as it reflects the last pass and may be stale
+        // (e.g. when called from a "Copy" button handler), Egui ignores the event if nothing is focused anyway.
+        // The query is only used for checking that the entity is an Egui context.
+        self.wants_input.get(context)?;
+        self.push(context, event);
+        Ok(())
+    }
+}
+
 #[derive(SystemParam)]
 /// A helper system param to iterate over pairs of events and Egui contexts, see [`EguiContextsEventIterator`].
 pub struct EguiContextEventReader<'w, 's, E: BufferedEvent> {
     event_reader: EventReader<'w, 's, E>,
+    resent_events: Option<Res<'w, EguiResentEvents<E>>>,
     map: Res<'w, WindowToEguiContextMap>,
     hovered_non_window_egui_context: Option<Res<'w, HoveredNonWindowEguiContext>>,
     focused_non_window_egui_context: Option<Res<'w, FocusedNonWindowEguiContext>>,
@@ -268,7 +562,11 @@ impl<'w, 's, E: BufferedEvent> EguiContextEventReader<'w, 's, E> {
         E: BufferedEvent,
     {
         EguiContextsEventIterator {
-            event_iter: self.event_reader.read(),
+            event_iter: self.event_reader.read_with_id(),
+            resent_event_ids: self
+                .resent_events
+                .as_deref()
+                .map_or(0..0, |resent_events| resent_events.ids.clone()),
             map_event_to_window_id_f,
             current_event: None,
             current_event_contexts: Vec::new(),
@@ -287,7 +585,11 @@ impl<'w, 's, E: BufferedEvent> EguiContextEventReader<'w, 's, E> {
         E: BufferedEvent,
     {
         EguiContextsEventIterator {
-            event_iter: self.event_reader.read(),
+            event_iter: self.event_reader.read_with_id(),
+            resent_event_ids: self
+                .resent_events
+                .as_deref()
+                .map_or(0..0, |resent_events| resent_events.ids.clone()),
             map_event_to_window_id_f,
             current_event: None,
             current_event_contexts: Vec::new(),
@@ -309,7 +611,11 @@ impl<'w, 's, E: BufferedEvent> EguiContextEventReader<'w, 's, E> {
         E: Event,
     {
         EguiContextsEventIterator {
-            event_iter: self.event_reader.read(),
+            event_iter: self.event_reader.read_with_id(),
+            resent_event_ids: self
+                .resent_events
+                .as_deref()
+                .map_or(0..0, |resent_events| resent_events.ids.clone()),
             map_event_to_window_id_f,
             current_event: None,
             current_event_contexts: Vec::new(),
@@ -327,6 +633,7 @@ pub fn write_modifiers_keys_state_system(
     mut ev_keyboard_input: EventReader<KeyboardInput>,
     mut ev_focus: EventReader<KeyboardFocusLost>,
     mut modifier_keys_state: ResMut<ModifierKeysState>,
+    resent_keyboard_input_events: Option<Res<EguiResentEvents<KeyboardInput>>>,
 ) {
     // If window focus is lost, clear all modifiers to avoid stuck keys.
     if !ev_focus.is_empty() {
@@ -334,7 +641,13 @@ pub fn write_modifiers_keys_state_system(
         modifier_keys_state.reset();
     }
 
-    for event in ev_keyboard_input.read() {
+    for (event, id) in ev_keyboard_input.read_with_id() {
+        if resent_keyboard_input_events
+            .as_deref()
+            .is_some_and(|resent_events| resent_events.contains(id))
+        {
+            continue;
+        }
         let KeyboardInput {
             logical_key, state, ..
         } = event;
@@ -359,33 +672,87 @@ pub fn write_modifiers_keys_state_system(
 /// Reads [`MouseButtonInput`] events and wraps them into [`EguiInputEvent`] (only for window contexts).
 pub fn write_window_pointer_moved_events_system(
     mut cursor_moved_reader: EguiContextEventReader<CursorMoved>,
+    mut cursor_left_reader: EventReader<CursorLeft>,
     mut egui_input_event_writer: EventWriter<EguiInputEvent>,
     mut egui_contexts: Query<
-        (&EguiContextSettings, &mut EguiContextPointerPosition),
+        (
+            &EguiContextSettings,
+            &EguiContextComputedScaleFactor,
+            &mut EguiContextPointerPosition,
+            &mut EguiContextMousePosition,
+            Option<&mut EguiContextPointerTrail>,
+        ),
         With<EguiContext>,
     >,
+    windows: Query<&Window>,
 ) {
+    for (_, _, _, _, pointer_trail) in egui_contexts.iter_mut() {
+        if let Some(mut pointer_trail) = pointer_trail {
+            pointer_trail.0.clear();
+        }
+    }
+
+    for event in cursor_left_reader.read() {
+        let Some(contexts) = cursor_moved_reader
+            .map
+            .window_to_contexts
+            .get(&event.window)
+        else {
+            continue;
+        };
+        for context in contexts {
+            if let Some((_, _, _, mut mouse_position, _)) = egui_contexts.get_some_mut(*context) {
+                mouse_position.last_position = None;
+            }
+        }
+    }
+
     for (event, context) in cursor_moved_reader.read(|event| event.window) {
-        let Some((context_settings, mut context_pointer_position)) =
-            egui_contexts.get_some_mut(context)
+        let Some((
+            context_settings,
+            computed_scale_factor,
+            mut context_pointer_position,
+            mut mouse_position,
+            pointer_trail,
+        )) = egui_contexts.get_some_mut(context)
         else {
             continue;
         };
 
-        if !context_settings
-            .input_system_settings
-            .run_write_window_pointer_moved_events_system
+        if !context_settings.input_enabled
+            || !context_settings
+                .input_system_settings
+                .run_write_window_pointer_moved_events_system
         {
             continue;
         }
 
-        let scale_factor = context_settings.scale_factor;
+        let Some(window) = windows.get_some(event.window) else {
+            continue;
+        };
+        let scale_factor = computed_scale_factor.scale_factor / window.resolution.scale_factor();
         let pointer_position = vec2_into_egui_pos2(event.position / scale_factor);
+        // The position isn't known after the cursor enters the window, so the first movement doesn't produce a delta.
+        let pointer_delta = mouse_position
+            .last_position
+            .replace(pointer_position)
+            .map_or(egui::Vec2::ZERO, |last_position| {
+                pointer_position - last_position
+            });
         context_pointer_position.position = pointer_position;
+        if let Some(mut pointer_trail) = pointer_trail {
+            pointer_trail.0.push(pointer_position);
+        }
         egui_input_event_writer.write(EguiInputEvent {
             context,
             event: egui::Event::PointerMoved(pointer_position),
         });
+        if context_settings.send_mouse_moved_events && pointer_delta != egui::Vec2::ZERO {
+            egui_input_event_writer.write(EguiInputEvent {
+                context,
+                event: egui::Event::MouseMoved(pointer_delta),
+            });
+        }
     }
 }
 
@@ -412,22 +779,15 @@ pub fn write_pointer_button_events_system(
             continue;
         };
 
-        if !context_settings
-            .input_system_settings
-            .run_write_pointer_button_events_system
+        if !context_settings.input_enabled
+            || !context_settings
+                .input_system_settings
+                .run_write_pointer_button_events_system
         {
             continue;
         }
 
-        let button = match event.button {
-            MouseButton::Left => Some(egui::PointerButton::Primary),
-            MouseButton::Right => Some(egui::PointerButton::Secondary),
-            MouseButton::Middle => Some(egui::PointerButton::Middle),
-            MouseButton::Back => Some(egui::PointerButton::Extra1),
-            MouseButton::Forward => Some(egui::PointerButton::Extra2),
-            _ => None,
-        };
-        let Some(button) = button else {
+        let Some(button) = crate::helpers::bevy_to_egui_pointer_button(event.button) else {
             continue;
         };
         let pressed = match event.state {
@@ -481,9 +841,10 @@ pub fn write_non_window_pointer_moved_events_system(
         return;
     };
 
-    if !context_settings
-        .input_system_settings
-        .run_write_non_window_pointer_moved_events_system
+    if !context_settings.input_enabled
+        || !context_settings
+            .input_system_settings
+            .run_write_non_window_pointer_moved_events_system
     {
         return;
     }
@@ -502,6 +863,8 @@ pub fn write_mouse_wheel_events_system(
     egui_contexts: Query<&EguiContextSettings, With<EguiContext>>,
 ) {
     let modifiers = modifier_keys_state.to_egui_modifiers();
+    // Deltas summed per context and unit, for contexts with `coalesce_mouse_wheel_events` enabled.
+    let mut coalesced_deltas = Vec::<(Entity, egui::MouseWheelUnit, egui::Vec2)>::new();
     for (event, context) in mouse_wheel_reader.read_with_non_window_hovered(|event| event.window) {
         let delta = egui::vec2(event.x, event.y);
         let unit = match event.unit {
@@ -513,13 +876,26 @@ pub fn write_mouse_wheel_events_system(
             continue;
         };
 
-        if !context_settings
-            .input_system_settings
-            .run_write_mouse_wheel_events_system
+        if !context_settings.input_enabled
+            || !context_settings
+                .input_system_settings
+                .run_write_mouse_wheel_events_system
         {
             continue;
         }
 
+        if context_settings.coalesce_mouse_wheel_events {
+            match coalesced_deltas
+                .iter_mut()
+                .find(|(coalesced_context, coalesced_unit, _)| {
+                    *coalesced_context == context && *coalesced_unit == unit
+                }) {
+                Some((_, _, coalesced_delta)) => *coalesced_delta += delta,
+                None => coalesced_deltas.push((context, unit, delta)),
+            }
+            continue;
+        }
+
         egui_input_event_writer.write(EguiInputEvent {
             context,
             event: egui::Event::MouseWheel {
@@ -529,10 +905,84 @@ pub fn write_mouse_wheel_events_system(
             },
         });
     }
+
+    egui_input_event_writer.write_batch(coalesced_deltas.into_iter().map(
+        |(context, unit, delta)| EguiInputEvent {
+            context,
+            event: egui::Event::MouseWheel {
+                unit,
+                delta,
+                modifiers,
+            },
+        },
+    ));
+}
+
+/// Reads [`PinchGesture`] events (trackpad pinch-to-zoom, only supported on macOS and iOS) and wraps them
+/// into [`EguiInputEvent`] as [`egui::Event::Zoom`].
+///
+/// Gestures aren't associated with windows, so the events are sent to [`HoveredNonWindowEguiContext`] if there's one,
+/// or to the contexts of focused windows otherwise.
+pub fn write_zoom_gesture_events_system(
+    mut pinch_gesture_reader: EventReader<PinchGesture>,
+    mut egui_input_event_writer: EventWriter<EguiInputEvent>,
+    hovered_non_window_egui_context: Option<Res<HoveredNonWindowEguiContext>>,
+    window_to_egui_context_map: Res<WindowToEguiContextMap>,
+    windows: Query<(Entity, &Window)>,
+    egui_contexts: Query<&EguiContextSettings, With<EguiContext>>,
+) {
+    if pinch_gesture_reader.is_empty() {
+        return;
+    }
+
+    let contexts = match hovered_non_window_egui_context.as_deref() {
+        Some(&HoveredNonWindowEguiContext(context)) => vec![context],
+        None => windows
+            .iter()
+            .filter(|(_, window)| window.focused)
+            .filter_map(|(window_entity, _)| {
+                window_to_egui_context_map
+                    .window_to_contexts
+                    .get(&window_entity)
+            })
+            .flatten()
+            .copied()
+            .collect(),
+    };
+    let contexts = contexts
+        .into_iter()
+        .filter(|context| {
+            egui_contexts.get_some(*context).is_some_and(|settings| {
+                settings.input_enabled
+                    && settings
+                        .input_system_settings
+                        .run_write_zoom_gesture_events_system
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for event in pinch_gesture_reader.read() {
+        // Aligned with the egui-winit implementation.
+        let zoom_factor = event.0.exp();
+        for context in &contexts {
+            egui_input_event_writer.write(EguiInputEvent {
+                context: *context,
+                event: egui::Event::Zoom(zoom_factor),
+            });
+        }
+    }
 }
 
 /// Reads [`KeyboardInput`] events and wraps them into [`EguiInputEvent`], can redirect events to [`FocusedNonWindowEguiContext`].
+///
+/// Modifier-only presses (e.g. Ctrl or Alt) aren't sent as [`egui::Event::Key`], as [`egui::Key`] has no variants
+/// for modifier keys. Their state is still passed to Egui on every pass (see [`write_modifiers_keys_state_system`]),
+/// so widgets can react to them by reading [`egui::InputState::modifiers`].
+///
+/// Keys that map to an [`egui::Key`] only physically (e.g. WASD on a non-Latin layout) are sent with the physical key.
+/// Keys that can't be mapped at all are sent as [`EguiUnmappedKeyEvent`].
 pub fn write_keyboard_input_events_system(
+    egui_global_settings: Res<EguiGlobalSettings>,
     modifier_keys_state: Res<ModifierKeysState>,
     #[cfg(all(
         feature = "manage_clipboard",
@@ -541,54 +991,120 @@ pub fn write_keyboard_input_events_system(
     ))]
     mut egui_clipboard: ResMut<crate::EguiClipboard>,
     mut keyboard_input_reader: EguiContextEventReader<KeyboardInput>,
+    mut keyboard_focus_lost_reader: EventReader<KeyboardFocusLost>,
     mut egui_input_event_writer: EventWriter<EguiInputEvent>,
-    egui_contexts: Query<&EguiContextSettings, With<EguiContext>>,
+    mut unmapped_key_event_writer: EventWriter<EguiUnmappedKeyEvent>,
+    mut egui_contexts: Query<
+        (
+            Entity,
+            &EguiContextSettings,
+            &EguiWantsInput,
+            &mut EguiContextKeyRepeatState,
+            Option<&EguiContextClipboard>,
+        ),
+        With<EguiContext>,
+    >,
+    time: Res<Time<Real>>,
 ) {
     let modifiers = modifier_keys_state.to_egui_modifiers();
+
+    // Key releases aren't received while windows are unfocused, so held keys would repeat forever.
+    if !keyboard_focus_lost_reader.is_empty() {
+        keyboard_focus_lost_reader.clear();
+        for (_, _, _, mut key_repeat_state, _) in egui_contexts.iter_mut() {
+            key_repeat_state.held_key = None;
+        }
+    }
+
     for (event, context) in keyboard_input_reader.read_with_non_window_focused(|event| event.window)
     {
-        let Some(context_settings) = egui_contexts.get_some(context) else {
+        let Some((
+            _,
+            context_settings,
+            context_wants_input,
+            mut key_repeat_state,
+            context_clipboard,
+        )) = egui_contexts.get_some_mut(context)
+        else {
             continue;
         };
 
-        if !context_settings
-            .input_system_settings
-            .run_write_keyboard_input_events_system
+        if !context_settings.input_enabled
+            || !context_settings
+                .input_system_settings
+                .run_write_keyboard_input_events_system
         {
+            key_repeat_state.held_key = None;
             continue;
         }
 
+        // If key repeat is synthesized by `bevy_egui`, OS repeat events are ignored.
+        if context_settings.key_repeat.is_some() && event.repeat {
+            continue;
+        }
+
+        let key = crate::helpers::bevy_to_egui_key(&event.logical_key);
+        let physical_key = crate::helpers::bevy_to_egui_physical_key(&event.key_code);
+        if [key, physical_key]
+            .into_iter()
+            .flatten()
+            .any(|key| context_settings.blocked_keys.contains(&key))
+        {
+            continue;
+        }
+
+        // The shortcut is handled by `cycle_focused_non_window_context_system`.
+        if let Some(shortcut) = &egui_global_settings.focus_cycle_shortcut {
+            if event.state.is_pressed() && matches_shortcut(shortcut, key, modifiers) {
+                continue;
+            }
+        }
+
+        if event.key_code == KeyCode::Escape
+            && !context_settings
+                .escape_behavior
+                .forwards_to_egui(context_wants_input)
+        {
+            continue;
+        }
+
+        let mut text = None;
         if modifier_keys_state.text_input_is_allowed() && event.state.is_pressed() {
             match &event.logical_key {
                 Key::Character(char) if char.matches(char::is_control).count() == 0 => {
-                    egui_input_event_writer.write(EguiInputEvent {
-                        context,
-                        event: egui::Event::Text(char.to_string()),
-                    });
+                    text = Some(char.to_string());
                 }
                 Key::Space => {
-                    egui_input_event_writer.write(EguiInputEvent {
-                        context,
-                        event: egui::Event::Text(" ".to_string()),
-                    });
+                    text = Some(" ".to_string());
                 }
                 _ => (),
             }
         }
-
-        let key = crate::helpers::bevy_to_egui_key(&event.logical_key);
-        let physical_key = crate::helpers::bevy_to_egui_physical_key(&event.key_code);
+        if let Some(text) = &text {
+            egui_input_event_writer.write(EguiInputEvent {
+                context,
+                event: egui::Event::Text(text.clone()),
+            });
+        }
 
         // "Logical OR physical key" is a fallback mechanism for keyboard layouts without Latin characters
         // See: https://github.com/emilk/egui/blob/66c73b9cbfbd4d44489fc6f6a840d7d82bc34389/crates/egui-winit/src/lib.rs#L760
         let (Some(key), physical_key) = (key.or(physical_key), physical_key) else {
+            unmapped_key_event_writer.write(EguiUnmappedKeyEvent {
+                context,
+                key_code: event.key_code,
+                logical_key: event.logical_key.clone(),
+                state: event.state,
+                repeat: event.repeat,
+                modifiers,
+            });
             continue;
         };
 
         let egui_event = egui::Event::Key {
             key,
             pressed: event.state.is_pressed(),
-            repeat: false,
+            repeat: event.repeat,
             modifiers,
             physical_key,
         };
@@ -597,14 +1113,32 @@ pub fn write_keyboard_input_events_system(
             event: egui_event,
         });
 
+        if let Some(key_repeat) = &context_settings.key_repeat {
+            if event.state.is_pressed() {
+                key_repeat_state.held_key = Some(HeldKey {
+                    key,
+                    physical_key,
+                    text,
+                    until_next_repeat: key_repeat.initial_delay,
+                });
+            } else if key_repeat_state
+                .held_key
+                .as_ref()
+                .is_some_and(|held_key| held_key.key == key)
+            {
+                key_repeat_state.held_key = None;
+            }
+        }
+
         // We also check that it's a `ButtonState::Pressed` event, as we don't want to
         // copy, cut or paste on the key release.
-        #[cfg(all(
-            feature = "manage_clipboard",
-            not(target_os = "android"),
-            not(target_arch = "wasm32")
-        ))]
-        if modifiers.command && event.state.is_pressed() {
+        let manages_clipboard = context_clipboard.is_some()
+            || cfg!(all(
+                feature = "manage_clipboard",
+                not(target_os = "android"),
+                not(target_arch = "wasm32")
+            ));
+        if manages_clipboard && modifiers.command && event.state.is_pressed() {
             match key {
                 egui::Key::C => {
                     egui_input_event_writer.write(EguiInputEvent {
@@ -619,7 +1153,22 @@ pub fn write_keyboard_input_events_system(
                     });
                 }
                 egui::Key::V => {
-                    if let Some(contents) = egui_clipboard.get_text() {
+                    let contents = match context_clipboard {
+                        Some(context_clipboard) => context_clipboard.text.clone(),
+                        #[cfg(all(
+                            feature = "manage_clipboard",
+                            not(target_os = "android"),
+                            not(target_arch = "wasm32")
+                        ))]
+                        None => egui_clipboard.get_text(),
+                        #[cfg(not(all(
+                            feature = "manage_clipboard",
+                            not(target_os = "android"),
+                            not(target_arch = "wasm32")
+                        )))]
+                        None => None,
+                    };
+                    if let Some(contents) = contents {
                         egui_input_event_writer.write(EguiInputEvent {
                             context,
                             event: egui::Event::Text(contents),
@@ -630,6 +1179,40 @@ pub fn write_keyboard_input_events_system(
             }
         }
     }
+
+    // Synthesize repeat events for held keys.
+    for (context, context_settings, _, mut key_repeat_state, _) in egui_contexts.iter_mut() {
+        let Some(key_repeat) = &context_settings.key_repeat else {
+            key_repeat_state.held_key = None;
+            continue;
+        };
+        let Some(held_key) = &mut key_repeat_state.held_key else {
+            continue;
+        };
+
+        held_key.until_next_repeat -= time.delta_secs();
+        if held_key.until_next_repeat <= 0.0 {
+            // Send at most one repeat per frame, repeats missed during frame hitches are dropped.
+            held_key.until_next_repeat =
+                (held_key.until_next_repeat + key_repeat.interval).max(0.0);
+            if let Some(text) = &held_key.text {
+                egui_input_event_writer.write(EguiInputEvent {
+                    context,
+                    event: egui::Event::Text(text.clone()),
+                });
+            }
+            egui_input_event_writer.write(EguiInputEvent {
+                context,
+                event: egui::Event::Key {
+                    key: held_key.key,
+                    pressed: true,
+                    repeat: true,
+                    modifiers,
+                    physical_key: held_key.physical_key,
+                },
+            });
+        }
+    }
 }
 
 /// Reads [`Ime`] events and wraps them into [`EguiInputEvent`], can redirect events to [`FocusedNonWindowEguiContext`].
@@ -658,9 +1241,11 @@ pub fn write_ime_events_system(
             continue;
         };
 
-        if !context_settings
-            .input_system_settings
-            .run_write_ime_events_system
+        if !context_settings.input_enabled
+            || !context_settings.ime_enabled
+            || !context_settings
+                .input_system_settings
+                .run_write_ime_events_system
         {
             continue;
         }
@@ -723,7 +1308,7 @@ pub fn write_ime_events_system(
 /// Works by reading [`EguiOutput`] and calling `Window::set_ime_allowed` if the `ime` field is set.
 #[cfg(any(target_os = "ios", target_os = "android"))]
 pub fn set_ime_allowed_system(
-    mut egui_context: Query<(&EguiOutput, &mut EguiContextImeState)>,
+    mut egui_context: Query<(&EguiOutput, &EguiContextSettings, &mut EguiContextImeState)>,
     windows: Query<Entity, With<bevy_window::PrimaryWindow>>,
     winit_windows: NonSendMut<bevy_winit::WinitWindows>,
 ) {
@@ -741,11 +1326,11 @@ pub fn set_ime_allowed_system(
         return;
     };
 
-    let Ok((egui_output, mut egui_ime_state)) = egui_context.single_mut() else {
+    let Ok((egui_output, context_settings, mut egui_ime_state)) = egui_context.single_mut() else {
         return;
     };
 
-    let ime_allowed = egui_output.platform_output.ime.is_some();
+    let ime_allowed = context_settings.ime_enabled && egui_output.platform_output.ime.is_some();
     if ime_allowed != egui_ime_state.is_ime_allowed {
         winit_window.set_ime_allowed(ime_allowed);
         egui_ime_state.is_ime_allowed = ime_allowed;
@@ -767,9 +1352,10 @@ pub fn write_file_dnd_events_system(
             continue;
         };
 
-        if !context_settings
-            .input_system_settings
-            .run_write_file_dnd_events_system
+        if !context_settings.input_enabled
+            || !context_settings
+                .input_system_settings
+                .run_write_file_dnd_events_system
         {
             continue;
         }
@@ -803,6 +1389,91 @@ pub fn write_file_dnd_events_system(
     }
 }
 
+/// Moves [`FocusedNonWindowEguiContext`] to the next (or previous) context listed in [`EguiFocusCycleContexts`]
+/// when [`EguiGlobalSettings::focus_cycle_shortcut`] is pressed.
+///
+/// Sends [`egui::Event::WindowFocused`] events to the contexts that lose and gain focus.
+#[allow(clippy::too_many_arguments)]
+pub fn cycle_focused_non_window_context_system(
+    mut commands: Commands,
+    egui_global_settings: Res<EguiGlobalSettings>,
+    modifier_keys_state: Res<ModifierKeysState>,
+    focus_cycle_contexts: Res<EguiFocusCycleContexts>,
+    focused_non_window_egui_context: Option<Res<FocusedNonWindowEguiContext>>,
+    mut keyboard_input_reader: EventReader<KeyboardInput>,
+    resent_keyboard_input_events: Option<Res<EguiResentEvents<KeyboardInput>>>,
+    mut egui_input_event_writer: EventWriter<EguiInputEvent>,
+    egui_contexts: Query<(), With<EguiContext>>,
+) {
+    let Some(shortcut) = egui_global_settings.focus_cycle_shortcut else {
+        return;
+    };
+    let modifiers = modifier_keys_state.to_egui_modifiers();
+    let contexts = focus_cycle_contexts
+        .0
+        .iter()
+        .copied()
+        .filter(|context| egui_contexts.contains(*context))
+        .collect::<Vec<_>>();
+
+    let initially_focused = focused_non_window_egui_context
+        .as_deref()
+        .map(|focused| focused.0);
+    let mut focused = initially_focused;
+    for (event, id) in keyboard_input_reader.read_with_id() {
+        if contexts.is_empty()
+            || resent_keyboard_input_events
+                .as_deref()
+                .is_some_and(|resent_events| resent_events.contains(id))
+            || !event.state.is_pressed()
+            || !matches_shortcut(
+                &shortcut,
+                crate::helpers::bevy_to_egui_key(&event.logical_key),
+                modifiers,
+            )
+        {
+            continue;
+        }
+
+        let backwards = modifiers.shift && !shortcut.modifiers.shift;
+        let len = contexts.len();
+        let next_index = match focused
+            .and_then(|focused| contexts.iter().position(|context| *context == focused))
+        {
+            Some(index) if backwards => (index + len - 1) % len,
+            Some(index) => (index + 1) % len,
+            None if backwards => len - 1,
+            None => 0,
+        };
+        focused = Some(contexts[next_index]);
+    }
+
+    if focused == initially_focused {
+        return;
+    }
+    if let Some(context) = initially_focused {
+        egui_input_event_writer.write(EguiInputEvent {
+            context,
+            event: egui::Event::WindowFocused(false),
+        });
+    }
+    if let Some(context) = focused {
+        commands.insert_resource(FocusedNonWindowEguiContext(context));
+        egui_input_event_writer.write(EguiInputEvent {
+            context,
+            event: egui::Event::WindowFocused(true),
+        });
+    }
+}
+
+fn matches_shortcut(
+    shortcut: &egui::KeyboardShortcut,
+    key: Option<egui::Key>,
+    modifiers: Modifiers,
+) -> bool {
+    key == Some(shortcut.logical_key) && modifiers.matches_logically(shortcut.modifiers)
+}
+
 /// Reads [`TouchInput`] events and wraps them into [`EguiInputEvent`].
 pub fn write_window_touch_events_system(
     mut commands: Commands,
@@ -813,12 +1484,16 @@ pub fn write_window_touch_events_system(
     mut egui_contexts: Query<
         (
             &EguiContextSettings,
+            &EguiContextComputedScaleFactor,
             &mut EguiContextPointerPosition,
             &mut EguiContextPointerTouchId,
+            &mut EguiContextMousePosition,
             &EguiOutput,
         ),
         With<EguiContext>,
     >,
+    windows: Query<&Window>,
+    time: Res<Time<Real>>,
 ) {
     let modifiers = modifier_keys_state.to_egui_modifiers();
     let hovered_non_window_egui_context = touch_input_reader
@@ -829,8 +1504,10 @@ pub fn write_window_touch_events_system(
     for (event, context) in touch_input_reader.read(|event| event.window) {
         let Some((
             context_settings,
+            computed_scale_factor,
             mut context_pointer_position,
             mut context_pointer_touch_id,
+            mut mouse_position,
             output,
         )) = egui_contexts.get_some_mut(context)
         else {
@@ -853,16 +1530,22 @@ pub fn write_window_touch_events_system(
             }
         }
 
-        if !context_settings
-            .input_system_settings
-            .run_write_window_touch_events_system
+        if !context_settings.input_enabled
+            || !context_settings
+                .input_system_settings
+                .run_write_window_touch_events_system
         {
             continue;
         }
 
-        let scale_factor = context_settings.scale_factor;
+        let Some(window) = windows.get_some(event.window) else {
+            continue;
+        };
+        let scale_factor = computed_scale_factor.scale_factor / window.resolution.scale_factor();
         let touch_position = vec2_into_egui_pos2(event.position / scale_factor);
         context_pointer_position.position = touch_position;
+        // Touches move the pointer, so the next mouse movement starts from an unknown position.
+        mouse_position.last_position = None;
         write_touch_event(
             &mut egui_input_event_writer,
             event,
@@ -870,6 +1553,8 @@ pub fn write_window_touch_events_system(
             output,
             touch_position,
             modifiers,
+            context_settings,
+            time.elapsed_secs_f64(),
             &mut context_pointer_touch_id,
         );
     }
@@ -890,6 +1575,7 @@ pub fn write_non_window_touch_events_system(
         ),
         With<EguiContext>,
     >,
+    time: Res<Time<Real>>,
 ) {
     let modifiers = modifier_keys_state.to_egui_modifiers();
     for event in touch_input_reader.read() {
@@ -909,9 +1595,10 @@ pub fn write_non_window_touch_events_system(
             continue;
         };
 
-        if !context_settings
-            .input_system_settings
-            .run_write_non_window_touch_events_system
+        if !context_settings.input_enabled
+            || !context_settings
+                .input_system_settings
+                .run_write_non_window_touch_events_system
         {
             continue;
         }
@@ -923,6 +1610,8 @@ pub fn write_non_window_touch_events_system(
             output,
             context_pointer_position.position,
             modifiers,
+            context_settings,
+            time.elapsed_secs_f64(),
             &mut context_pointer_touch_id,
         );
     }
@@ -935,10 +1624,30 @@ fn write_touch_event(
     _output: &EguiOutput,
     pointer_position: egui::Pos2,
     modifiers: Modifiers,
+    context_settings: &EguiContextSettings,
+    time: f64,
     context_pointer_touch_id: &mut EguiContextPointerTouchId,
 ) {
     let touch_id = egui::TouchId::from(event.id);
 
+    match event.phase {
+        bevy_input::touch::TouchPhase::Started => {
+            context_pointer_touch_id.active_touch_ids.insert(event.id);
+            // A new touch stops the scroll momentum.
+            if context_pointer_touch_id.inertia_velocity.take().is_some() {
+                egui_input_event_writer.write(EguiInputEvent {
+                    context,
+                    event: egui::Event::PointerGone,
+                });
+            }
+        }
+        bevy_input::touch::TouchPhase::Moved => {}
+        bevy_input::touch::TouchPhase::Ended | bevy_input::touch::TouchPhase::Canceled => {
+            context_pointer_touch_id.active_touch_ids.remove(&event.id);
+        }
+    }
+    let is_multi_touch = context_pointer_touch_id.active_touch_ids.len() > 1;
+
     // Emit the touch event.
     egui_input_event_writer.write(EguiInputEvent {
         context,
@@ -960,10 +1669,45 @@ fn write_touch_event(
                     ..
                 }) => Some((force / max_possible_force) as f32),
                 None => None,
-            },
+            }
+            .map(|force| match &context_settings.touch_force {
+                Some(touch_force) => touch_force.map_force(force),
+                None => force,
+            }),
         },
     });
 
+    // Release the emulated pointer once a multi-touch gesture begins, otherwise it stays pressed,
+    // as the touch that emulates it is ignored during the gesture.
+    if let (bevy_input::touch::TouchPhase::Started, true, Some(pointer_touch_id)) = (
+        event.phase,
+        is_multi_touch,
+        context_pointer_touch_id.pointer_touch_id,
+    ) {
+        if pointer_touch_id != event.id {
+            let pos = context_pointer_touch_id
+                .last_pointer_touch_sample
+                .map_or(pointer_position, |(position, _)| position);
+            context_pointer_touch_id.pointer_touch_id = None;
+            context_pointer_touch_id.inertia_velocity = None;
+            egui_input_event_writer.write_batch([
+                EguiInputEvent {
+                    context,
+                    event: egui::Event::PointerButton {
+                        pos,
+                        button: egui::PointerButton::Primary,
+                        pressed: false,
+                        modifiers,
+                    },
+                },
+                EguiInputEvent {
+                    context,
+                    event: egui::Event::PointerGone,
+                },
+            ]);
+        }
+    }
+
     // If we're not yet translating a touch, or we're translating this very
     // touch, …
     if context_pointer_touch_id.pointer_touch_id.is_none()
@@ -971,8 +1715,12 @@ fn write_touch_event(
     {
         // … emit PointerButton resp. PointerMoved events to emulate mouse.
         match event.phase {
+            // Don't start emulating a pointer if a multi-touch gesture is already in progress.
+            bevy_input::touch::TouchPhase::Started if is_multi_touch => {}
             bevy_input::touch::TouchPhase::Started => {
                 context_pointer_touch_id.pointer_touch_id = Some(event.id);
+                context_pointer_touch_id.last_pointer_touch_sample = Some((pointer_position, time));
+                context_pointer_touch_id.pointer_touch_velocity = egui::Vec2::ZERO;
                 // First move the pointer to the right location.
                 egui_input_event_writer.write(EguiInputEvent {
                     context,
@@ -989,12 +1737,29 @@ fn write_touch_event(
                     },
                 });
             }
+            // Let Egui handle multi-touch gestures with the touch events only.
+            bevy_input::touch::TouchPhase::Moved if is_multi_touch => {}
             bevy_input::touch::TouchPhase::Moved => {
+                if context_settings.pointer_latency_compensation.is_some()
+                    || context_settings.touch_inertia.is_some()
+                {
+                    update_pointer_touch_velocity(context_pointer_touch_id, pointer_position, time);
+                }
+                let pointer_position = match context_settings.pointer_latency_compensation {
+                    Some(prediction_time) => {
+                        pointer_position
+                            + context_pointer_touch_id.pointer_touch_velocity * prediction_time
+                    }
+                    None => pointer_position,
+                };
                 egui_input_event_writer.write(EguiInputEvent {
                     context,
                     event: egui::Event::PointerMoved(pointer_position),
                 });
             }
+            // The pointer was already released when a multi-touch gesture began.
+            bevy_input::touch::TouchPhase::Ended | bevy_input::touch::TouchPhase::Canceled
+                if context_pointer_touch_id.pointer_touch_id.is_none() => {}
             bevy_input::touch::TouchPhase::Ended => {
                 context_pointer_touch_id.pointer_touch_id = None;
                 egui_input_event_writer.write(EguiInputEvent {
@@ -1006,10 +1771,22 @@ fn write_touch_event(
                         modifiers,
                     },
                 });
-                egui_input_event_writer.write(EguiInputEvent {
-                    context,
-                    event: egui::Event::PointerGone,
-                });
+                // Keep the pointer hovering the released position while the momentum is applied,
+                // as Egui scrolls only the hovered scroll areas.
+                // The pointer is removed by `write_touch_inertia_events_system` once the momentum fades.
+                if let Some(touch_inertia) = &context_settings.touch_inertia {
+                    update_pointer_touch_velocity(context_pointer_touch_id, pointer_position, time);
+                    let velocity = context_pointer_touch_id.pointer_touch_velocity;
+                    if velocity.length() >= touch_inertia.min_velocity {
+                        context_pointer_touch_id.inertia_velocity = Some(velocity);
+                    }
+                }
+                if context_pointer_touch_id.inertia_velocity.is_none() {
+                    egui_input_event_writer.write(EguiInputEvent {
+                        context,
+                        event: egui::Event::PointerGone,
+                    });
+                }
 
                 #[cfg(target_arch = "wasm32")]
                 if !is_mobile_safari() {
@@ -1030,64 +1807,169 @@ fn write_touch_event(
     }
 }
 
+/// Synthesizes decaying [`egui::Event::MouseWheel`] events after a touch that emulates the pointer ends,
+/// see [`EguiContextSettings::touch_inertia`].
+pub fn write_touch_inertia_events_system(
+    modifier_keys_state: Res<ModifierKeysState>,
+    mut egui_input_event_writer: EventWriter<EguiInputEvent>,
+    mut egui_contexts: Query<
+        (Entity, &EguiContextSettings, &mut EguiContextPointerTouchId),
+        With<EguiContext>,
+    >,
+    time: Res<Time<Real>>,
+) {
+    let modifiers = modifier_keys_state.to_egui_modifiers();
+    let delta_secs = time.delta_secs();
+    for (context, context_settings, mut context_pointer_touch_id) in egui_contexts.iter_mut() {
+        let Some(velocity) = context_pointer_touch_id.inertia_velocity else {
+            continue;
+        };
+
+        let velocity = match &context_settings.touch_inertia {
+            Some(touch_inertia) if context_settings.input_enabled => {
+                Some(velocity * (-touch_inertia.friction * delta_secs).exp())
+                    .filter(|velocity| velocity.length() >= touch_inertia.min_velocity)
+            }
+            _ => None,
+        };
+        context_pointer_touch_id.inertia_velocity = velocity;
+
+        let Some(velocity) = velocity else {
+            egui_input_event_writer.write(EguiInputEvent {
+                context,
+                event: egui::Event::PointerGone,
+            });
+            continue;
+        };
+        egui_input_event_writer.write(EguiInputEvent {
+            context,
+            event: egui::Event::MouseWheel {
+                unit: egui::MouseWheelUnit::Point,
+                delta: velocity * delta_secs,
+                modifiers,
+            },
+        });
+    }
+}
+
+/// Updates the velocity of the touch that emulates the pointer.
+fn update_pointer_touch_velocity(
+    context_pointer_touch_id: &mut EguiContextPointerTouchId,
+    pointer_position: egui::Pos2,
+    time: f64,
+) {
+    // Touches may have several move events per frame, which share the same timestamp,
+    // so we keep the velocity and the last sample until the time advances.
+    match context_pointer_touch_id.last_pointer_touch_sample {
+        Some((last_position, last_time)) if time > last_time => {
+            let velocity = (pointer_position - last_position) / (time - last_time) as f32;
+            // Smooth the velocity a bit to avoid jitter caused by irregular event timing.
+            context_pointer_touch_id.pointer_touch_velocity =
+                (context_pointer_touch_id.pointer_touch_velocity + velocity) * 0.5;
+            context_pointer_touch_id.last_pointer_touch_sample = Some((pointer_position, time));
+        }
+        Some((_, last_time)) => {
+            context_pointer_touch_id.last_pointer_touch_sample =
+                Some((pointer_position, last_time));
+        }
+        None => {
+            context_pointer_touch_id.last_pointer_touch_sample = Some((pointer_position, time));
+        }
+    }
+}
+
 /// Reads both [`EguiFileDragAndDropEvent`] and [`EguiInputEvent`] events and feeds them to Egui.
+///
+/// For hovered and dropped files, [`egui::Event::PointerMoved`] with the last [`EguiContextPointerPosition`]
+/// is sent as well, as Egui positions its file drag preview (and drop targets) by the pointer position,
+/// and some platforms don't send cursor events while files are being dragged.
 #[allow(clippy::too_many_arguments)]
 pub fn write_egui_input_system(
     focused_non_window_egui_context: Option<Res<FocusedNonWindowEguiContext>>,
     window_to_egui_context_map: Res<WindowToEguiContextMap>,
     modifier_keys_state: Res<ModifierKeysState>,
+    dropped_file_transform: Option<Res<EguiDroppedFileTransform>>,
     mut egui_input_event_reader: EventReader<EguiInputEvent>,
     mut egui_file_dnd_event_reader: EventReader<EguiFileDragAndDropEvent>,
-    mut egui_contexts: Query<(Entity, &mut EguiInput)>,
+    mut egui_contexts: Query<(Entity, &mut EguiInput, &EguiContextPointerPosition)>,
+    mut input_stats: Query<&mut EguiInputStats>,
     windows: Query<&Window>,
     time: Res<Time<Real>>,
 ) {
+    for mut stats in input_stats.iter_mut() {
+        stats.set_if_neq(EguiInputStats::default());
+    }
+
     for EguiInputEvent { context, event } in egui_input_event_reader.read() {
         #[cfg(feature = "log_input_events")]
         log::warn!("{context:?}: {event:?}");
 
-        let (_, mut egui_input) = match egui_contexts.get_mut(*context) {
+        let (_, mut egui_input, _) = match egui_contexts.get_mut(*context) {
             Ok(egui_input) => egui_input,
             Err(err) => {
                 log::error!(
                     "Failed to get an Egui context ({context:?}) for an event ({event:?}): {err:?}"
                 );
+                if let Some(mut stats) = input_stats.get_some_mut(*context) {
+                    stats.dropped_for_missing_context += 1;
+                }
                 continue;
             }
         };
 
         egui_input.events.push(event.clone());
+        if let Some(mut stats) = input_stats.get_some_mut(*context) {
+            stats.events_this_frame += 1;
+        }
     }
 
     for EguiFileDragAndDropEvent { context, event } in egui_file_dnd_event_reader.read() {
         #[cfg(feature = "log_file_dnd_events")]
         log::warn!("{context:?}: {event:?}");
 
-        let (_, mut egui_input) = match egui_contexts.get_mut(*context) {
+        let (_, mut egui_input, pointer_position) = match egui_contexts.get_mut(*context) {
             Ok(egui_input) => egui_input,
             Err(err) => {
                 log::error!(
                     "Failed to get an Egui context ({context:?}) for an event ({event:?}): {err:?}"
                 );
+                if let Some(mut stats) = input_stats.get_some_mut(*context) {
+                    stats.dropped_for_missing_context += 1;
+                }
                 continue;
             }
         };
+        if let Some(mut stats) = input_stats.get_some_mut(*context) {
+            stats.events_this_frame += 1;
+        }
 
         match event {
             FileDragAndDrop::DroppedFile {
                 window: _,
                 path_buf,
             } => {
+                egui_input
+                    .events
+                    .push(egui::Event::PointerMoved(pointer_position.position));
                 egui_input.hovered_files.clear();
-                egui_input.dropped_files.push(egui::DroppedFile {
+                let dropped_file = egui::DroppedFile {
                     path: Some(path_buf.clone()),
                     ..Default::default()
-                });
+                };
+                egui_input
+                    .dropped_files
+                    .push(match dropped_file_transform.as_deref() {
+                        Some(EguiDroppedFileTransform(transform)) => transform(dropped_file),
+                        None => dropped_file,
+                    });
             }
             FileDragAndDrop::HoveredFile {
                 window: _,
                 path_buf,
             } => {
+                egui_input
+                    .events
+                    .push(egui::Event::PointerMoved(pointer_position.position));
                 egui_input.hovered_files.push(egui::HoveredFile {
                     path: Some(path_buf.clone()),
                     ..Default::default()
@@ -1099,7 +1981,7 @@ pub fn write_egui_input_system(
         }
     }
 
-    for (entity, mut egui_input) in egui_contexts.iter_mut() {
+    for (entity, mut egui_input, _) in egui_contexts.iter_mut() {
         egui_input.focused = focused_non_window_egui_context.as_deref().map_or_else(
             || {
                 window_to_egui_context_map
@@ -1115,11 +1997,64 @@ pub fn write_egui_input_system(
     }
 }
 
+/// Stores the ids of the events that [`absorb_bevy_input_system`] re-sent to pass them through to the app.
+///
+/// `bevy_egui` reads the events before they are absorbed, so its readers skip the re-sent ones
+/// instead of feeding them to Egui twice.
+#[derive(Resource)]
+pub struct EguiResentEvents<E: BufferedEvent> {
+    ids: std::ops::Range<usize>,
+    marker: std::marker::PhantomData<E>,
+}
+
+impl<E: BufferedEvent> Default for EguiResentEvents<E> {
+    fn default() -> Self {
+        Self {
+            ids: 0..0,
+            marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<E: BufferedEvent> EguiResentEvents<E> {
+    /// Returns `true` if the event with the given id was re-sent by [`absorb_bevy_input_system`].
+    pub fn contains(&self, id: EventId<E>) -> bool {
+        self.ids.contains(&id.id)
+    }
+}
+
+/// Removes the events of the current update matching `is_absorbed`, while keeping the other ones.
+///
+/// [`Events`] can't remove individual events, so the buffers are cleared and the events passing through
+/// are re-sent (with new ids, which are stored in [`EguiResentEvents`]). Events of the previous update are dropped.
+fn absorb_current_update_events<E: BufferedEvent + Clone>(
+    events: &mut Events<E>,
+    resent_events: &mut EguiResentEvents<E>,
+    is_absorbed: impl Fn(&E) -> bool,
+) {
+    let passing_events = events
+        .iter_current_update_events()
+        .filter(|event| !is_absorbed(event))
+        .cloned()
+        .collect::<Vec<_>>();
+    events.clear();
+    if passing_events.is_empty() {
+        return;
+    }
+    let mut cursor = events.get_cursor_current();
+    events.extend(passing_events);
+    let ids = cursor
+        .read_with_id(events)
+        .map(|(_, id)| id.id)
+        .collect::<Vec<_>>();
+    resent_events.ids = ids[0]..ids[ids.len() - 1] + 1;
+}
+
 /// Clears Bevy input event buffers and resets [`ButtonInput`] resources if Egui
 /// is using pointer or keyboard (see the [`write_egui_wants_input_system`] run condition).
 ///
 /// This system isn't run by default, set [`EguiGlobalSettings::enable_absorb_bevy_input_system`]
-/// to `true` to enable it.
+/// to `true` to enable it. Which inputs get absorbed is configured with [`EguiGlobalSettings::absorb_input_settings`].
 ///
 /// ## Considerations
 ///
@@ -1128,18 +2063,36 @@ pub fn write_egui_input_system(
 /// clearing events the same way that might be in conflict with `bevy_egui`, and there's
 /// no other system that needs a non-interrupted flow of events.
 ///
+/// If only some of the [`MouseButtonInput`] or [`KeyboardInput`] events of the current update are absorbed (see
+/// [`EguiContextSettings::capture_buttons`] and [`EguiContextSettings::escape_behavior`]), the other ones
+/// are re-sent (see [`EguiResentEvents`]).
+/// Systems reading the events before this one (e.g. the ones updating [`ButtonInput`] in [`bevy_input::InputSystems`])
+/// see the re-sent events again in the next update, which doesn't change the state of buttons that are still held.
+///
 /// ## Alternative
 ///
 /// A safer alternative is to apply `run_if(not(egui_wants_any_pointer_input))` or `run_if(not(egui_wants_any_keyboard_input))` to your systems
 /// that need to be disabled while Egui is using input (see the [`egui_wants_any_pointer_input`], [`egui_wants_any_keyboard_input`] run conditions).
+#[allow(clippy::too_many_arguments)]
 pub fn absorb_bevy_input_system(
+    egui_global_settings: Res<EguiGlobalSettings>,
     egui_wants_input: Res<EguiWantsInput>,
     mut mouse_input: ResMut<ButtonInput<MouseButton>>,
     mut keyboard_input: ResMut<ButtonInput<KeyCode>>,
     mut keyboard_input_events: ResMut<Events<KeyboardInput>>,
+    mut resent_keyboard_input_events: ResMut<EguiResentEvents<KeyboardInput>>,
     mut mouse_wheel_events: ResMut<Events<MouseWheel>>,
     mut mouse_button_input_events: ResMut<Events<MouseButtonInput>>,
+    mut resent_mouse_button_input_events: ResMut<EguiResentEvents<MouseButtonInput>>,
+    mut mouse_motion_events: Option<ResMut<Events<MouseMotion>>>,
+    mut touch_input_events: Option<ResMut<Events<TouchInput>>>,
+    mut touches: Option<ResMut<Touches>>,
+    mut gamepad_button_events: Option<ResMut<Events<GamepadButtonChangedEvent>>>,
+    mut gamepad_axis_events: Option<ResMut<Events<GamepadAxisChangedEvent>>>,
+    mut gamepads: Query<&mut Gamepad>,
+    egui_contexts: Query<(&EguiContextSettings, &EguiWantsInput)>,
 ) {
+    let settings = &egui_global_settings.absorb_input_settings;
     let modifiers = [
         KeyCode::SuperLeft,
         KeyCode::SuperRight,
@@ -1156,13 +2109,85 @@ pub fn absorb_bevy_input_system(
     // TODO: the list of events is definitely not comprehensive, but it should at least cover
     //  the most popular use-cases. We can add more on request.
     if egui_wants_input.wants_any_keyboard_input() {
-        keyboard_input.reset_all();
-        keyboard_input_events.clear();
+        if settings.keyboard {
+            // Escape is absorbed only if it's consumed by a context (see `EguiContextSettings::escape_behavior`).
+            let absorb_escape = egui_contexts.iter().any(|(context_settings, wants_input)| {
+                context_settings.escape_behavior != EguiEscapeBehavior::Game
+                    && wants_input.wants_any_keyboard_input()
+            });
+            if absorb_escape {
+                keyboard_input.reset_all();
+            } else {
+                let keys = keyboard_input
+                    .get_pressed()
+                    .chain(keyboard_input.get_just_pressed())
+                    .chain(keyboard_input.get_just_released())
+                    .filter(|key| **key != KeyCode::Escape)
+                    .copied()
+                    .collect::<Vec<_>>();
+                for key in keys {
+                    keyboard_input.reset(key);
+                }
+            }
+            absorb_current_update_events(
+                &mut keyboard_input_events,
+                &mut resent_keyboard_input_events,
+                |event| absorb_escape || event.key_code != KeyCode::Escape,
+            );
+        }
+        if settings.gamepad {
+            for mut gamepad in gamepads.iter_mut() {
+                gamepad.digital_mut().reset_all();
+            }
+            if let Some(events) = gamepad_button_events.as_deref_mut() {
+                events.clear();
+            }
+            if let Some(events) = gamepad_axis_events.as_deref_mut() {
+                events.clear();
+            }
+        }
     }
     if egui_wants_input.wants_any_pointer_input() {
-        mouse_input.reset_all();
+        if settings.mouse_buttons {
+            // Buttons that none of the contexts wanting pointer input capture pass through.
+            let is_captured = |button: MouseButton| {
+                egui_contexts.iter().any(|(context_settings, wants_input)| {
+                    wants_input.wants_any_pointer_input()
+                        && context_settings.captures_button(button)
+                })
+            };
+            let buttons = mouse_input
+                .get_pressed()
+                .chain(mouse_input.get_just_pressed())
+                .chain(mouse_input.get_just_released())
+                .copied()
+                .filter(|button| is_captured(*button))
+                .collect::<Vec<_>>();
+            for button in buttons {
+                mouse_input.reset(button);
+            }
+            absorb_current_update_events(
+                &mut mouse_button_input_events,
+                &mut resent_mouse_button_input_events,
+                |event| is_captured(event.button),
+            );
+        }
+        if settings.mouse_motion {
+            if let Some(events) = mouse_motion_events.as_deref_mut() {
+                events.clear();
+            }
+        }
+        if settings.touch {
+            if let Some(events) = touch_input_events.as_deref_mut() {
+                events.clear();
+            }
+            if let Some(touches) = touches.as_deref_mut() {
+                touches.reset_all();
+            }
+        }
+    }
+    if settings.mouse_wheel && egui_wants_input.wants_scroll() {
         mouse_wheel_events.clear();
-        mouse_button_input_events.clear();
     }
 
     for key in pressed.into_iter().flatten() {
@@ -1171,13 +2196,18 @@ pub fn absorb_bevy_input_system(
 }
 
 /// Stores whether there's an Egui context using pointer or keyboard.
-#[derive(Resource, Clone, Debug, Default)]
+///
+/// As a resource, it combines the state of all contexts. As a component (inserted for each [`EguiContext`]),
+/// it stores the state of a single context, e.g. query `&EguiWantsInput` to check
+/// [`EguiWantsInput::is_pointer_over_area`] for a specific context.
+#[derive(Resource, Component, Clone, Debug, Default)]
 pub struct EguiWantsInput {
     is_pointer_over_area: bool,
     wants_pointer_input: bool,
     is_using_pointer: bool,
     wants_keyboard_input: bool,
     is_popup_open: bool,
+    wants_scroll: bool,
 }
 
 impl EguiWantsInput {
@@ -1219,6 +2249,19 @@ impl EguiWantsInput {
         self.is_popup_open
     }
 
+    /// Returns `true` if Egui wants to consume scroll input.
+    ///
+    /// For contexts with [`EguiContextSettings::capture_scroll_only_over_scroll_areas`] enabled,
+    /// this is `true` only if Egui consumed the last scroll at the current pointer position (e.g. a scroll area
+    /// that can still scroll took it), if Egui is using the pointer, or if a popup is open. Empty panel space
+    /// doesn't count. Until the pointer is scrolled after moving, an Egui area is assumed to consume scroll,
+    /// see [`EguiContextScrollState`] for the details.
+    /// The value is updated after each pass, so it applies to the scroll input of the next frame.
+    /// Otherwise, it matches [`EguiWantsInput::wants_any_pointer_input`].
+    pub fn wants_scroll(&self) -> bool {
+        self.wants_scroll
+    }
+
     /// Returns `true` if any of the following is true:
     /// [`EguiWantsInput::is_pointer_over_area`], [`EguiWantsInput::wants_pointer_input`], [`EguiWantsInput::is_using_pointer`], [`EguiWantsInput::is_context_menu_open`].
     pub fn wants_any_pointer_input(&self) -> bool {
@@ -1240,33 +2283,169 @@ impl EguiWantsInput {
         self.wants_any_pointer_input() || self.wants_any_keyboard_input()
     }
 
+    fn claims_pointer(&self) -> bool {
+        self.is_pointer_over_area || self.wants_pointer_input || self.is_using_pointer
+    }
+
+    fn release_pointer(&mut self) {
+        self.is_pointer_over_area = false;
+        self.wants_pointer_input = false;
+        self.is_using_pointer = false;
+        self.wants_scroll = false;
+    }
+
     fn reset(&mut self) {
         self.is_pointer_over_area = false;
         self.wants_pointer_input = false;
         self.is_using_pointer = false;
         self.wants_keyboard_input = false;
         self.is_popup_open = false;
+        self.wants_scroll = false;
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.is_pointer_over_area |= other.is_pointer_over_area;
+        self.wants_pointer_input |= other.wants_pointer_input;
+        self.is_using_pointer |= other.is_using_pointer;
+        self.wants_keyboard_input |= other.wants_keyboard_input;
+        self.is_popup_open |= other.is_popup_open;
+        self.wants_scroll |= other.wants_scroll;
     }
 }
 
-/// Updates the [`EguiWantsInput`] resource.
+/// Stores whether an Egui context consumes scroll input at the current pointer position,
+/// is used for [`EguiWantsInput::wants_scroll`] (see [`EguiContextSettings::capture_scroll_only_over_scroll_areas`]).
+///
+/// The state is updated by Egui pass callbacks installed in [`setup_scroll_callbacks_system`]:
+/// a scroll counts as consumed if a widget (e.g. [`egui::ScrollArea`]) took [`egui::InputState::smooth_scroll_delta`]
+/// during the pass. The outcome is reset when the pointer moves or leaves Egui areas.
+#[derive(Component, Clone, Default)]
+pub struct EguiContextScrollState(Arc<Mutex<ScrollState>>);
+
+impl EguiContextScrollState {
+    /// Returns whether the last scroll at the current pointer position was consumed,
+    /// `None` if there was no scroll since the pointer moved.
+    pub fn consumes_scroll(&self) -> Option<bool> {
+        self.0.lock().unwrap().consumes_scroll
+    }
+}
+
+#[derive(Default)]
+struct ScrollState {
+    pass_start_scroll_delta: egui::Vec2,
+    consumes_scroll: Option<bool>,
+}
+
+/// Installs pass callbacks that update [`EguiContextScrollState`] for new contexts.
+pub fn setup_scroll_callbacks_system(
+    mut contexts: Query<(&mut EguiContext, &EguiContextScrollState), Added<EguiContext>>,
+) {
+    for (mut context, scroll_state) in contexts.iter_mut() {
+        let ctx = context.get_mut();
+        let state = scroll_state.0.clone();
+        ctx.on_begin_pass(
+            "bevy_egui_scroll_state",
+            Arc::new(move |ctx| {
+                state.lock().unwrap().pass_start_scroll_delta =
+                    ctx.input(|input| input.smooth_scroll_delta);
+            }),
+        );
+        let state = scroll_state.0.clone();
+        ctx.on_end_pass(
+            "bevy_egui_scroll_state",
+            Arc::new(move |ctx| {
+                let mut state = state.lock().unwrap();
+                let (pointer_delta, scroll_delta) =
+                    ctx.input(|input| (input.pointer.delta(), input.smooth_scroll_delta));
+                if pointer_delta != egui::Vec2::ZERO || !ctx.is_pointer_over_area() {
+                    state.consumes_scroll = None;
+                }
+                // Passes without scroll input (e.g. the extra passes of multi-pass contexts) keep the last outcome.
+                if state.pass_start_scroll_delta != egui::Vec2::ZERO {
+                    state.consumes_scroll =
+                        Some(scroll_delta.length() < state.pass_start_scroll_delta.length());
+                }
+            }),
+        );
+    }
+}
+
+/// Updates the [`EguiWantsInput`] resource and components.
+///
+/// If several contexts rendering to the same window claim the pointer (e.g. stacked cameras with overlapping UIs),
+/// only the ones with the highest [`EguiContextSettings::pointer_capture_priority`] keep the pointer-related flags.
 pub fn write_egui_wants_input_system(
-    mut egui_context_query: Query<&mut EguiContext>,
+    mut egui_context_query: Query<(
+        Entity,
+        &mut EguiContext,
+        &EguiContextSettings,
+        &EguiContextScrollState,
+        &mut EguiWantsInput,
+    )>,
     mut egui_wants_input: ResMut<EguiWantsInput>,
+    window_to_egui_context_map: Res<WindowToEguiContextMap>,
 ) {
     egui_wants_input.reset();
 
-    for mut ctx in egui_context_query.iter_mut() {
+    for (_, mut ctx, settings, scroll_state, mut context_wants_input) in
+        egui_context_query.iter_mut()
+    {
         let egui_ctx = ctx.get_mut();
-        egui_wants_input.is_pointer_over_area =
-            egui_wants_input.is_pointer_over_area || egui_ctx.is_pointer_over_area();
-        egui_wants_input.wants_pointer_input =
-            egui_wants_input.wants_pointer_input || egui_ctx.wants_pointer_input();
-        egui_wants_input.is_using_pointer =
-            egui_wants_input.is_using_pointer || egui_ctx.is_using_pointer();
-        egui_wants_input.wants_keyboard_input =
-            egui_wants_input.wants_keyboard_input || egui_ctx.wants_keyboard_input();
-        egui_wants_input.is_popup_open = egui_wants_input.is_popup_open || egui_ctx.is_popup_open();
+        let is_using_pointer = egui_ctx.is_using_pointer();
+        let (is_pointer_over_area, wants_pointer_input) = if settings.wants_pointer_includes_hover {
+            (
+                egui_ctx.is_pointer_over_area(),
+                egui_ctx.wants_pointer_input(),
+            )
+        } else {
+            // Hovering alone doesn't count, only actual interaction does.
+            (false, egui_ctx.wants_pointer_input() && is_using_pointer)
+        };
+        let is_popup_open = egui_ctx.is_popup_open();
+        let wants_scroll = if settings.capture_scroll_only_over_scroll_areas {
+            // Scrolling over an area is assumed to be consumed until it's tried.
+            is_using_pointer
+                || is_popup_open
+                || (egui_ctx.is_pointer_over_area()
+                    && scroll_state.consumes_scroll().unwrap_or(true))
+        } else {
+            is_pointer_over_area || wants_pointer_input || is_using_pointer || is_popup_open
+        };
+
+        *context_wants_input = EguiWantsInput {
+            is_pointer_over_area,
+            wants_pointer_input,
+            is_using_pointer,
+            wants_keyboard_input: egui_ctx.wants_keyboard_input(),
+            is_popup_open,
+            wants_scroll,
+        };
+    }
+
+    let mut window_pointer_capture_priorities =
+        bevy_platform::collections::HashMap::<Entity, i32>::default();
+    for (entity, _, settings, _, context_wants_input) in egui_context_query.iter() {
+        if !context_wants_input.claims_pointer() {
+            continue;
+        }
+        if let Some(window) = window_to_egui_context_map.context_to_window.get(&entity) {
+            let priority = window_pointer_capture_priorities
+                .entry(*window)
+                .or_insert(settings.pointer_capture_priority);
+            *priority = (*priority).max(settings.pointer_capture_priority);
+        }
+    }
+
+    for (entity, _, settings, _, mut context_wants_input) in egui_context_query.iter_mut() {
+        let is_overlapped = window_to_egui_context_map
+            .context_to_window
+            .get(&entity)
+            .and_then(|window| window_pointer_capture_priorities.get(window))
+            .is_some_and(|priority| settings.pointer_capture_priority < *priority);
+        if is_overlapped {
+            context_wants_input.release_pointer();
+        }
+        egui_wants_input.merge(&context_wants_input);
     }
 }
 
@@ -1282,8 +2461,57 @@ pub fn egui_wants_any_keyboard_input(egui_wants_input_resource: Res<EguiWantsInp
     egui_wants_input_resource.wants_any_keyboard_input()
 }
 
+/// Returns `true` if Egui wants to consume scroll input, see [`EguiWantsInput::wants_scroll`].
+pub fn egui_wants_scroll(egui_wants_input_resource: Res<EguiWantsInput>) -> bool {
+    egui_wants_input_resource.wants_scroll()
+}
+
 /// Returns `true` if any of the following is true:
 /// [`EguiWantsInput::wants_any_pointer_input`], [`EguiWantsInput::wants_any_keyboard_input`].
 pub fn egui_wants_any_input(egui_wants_input_resource: Res<EguiWantsInput>) -> bool {
     egui_wants_input_resource.wants_any_input()
 }
+
+/// Releases the cursor grab of the primary window while Egui wants pointer input
+/// (see [`EguiWantsInput::wants_any_pointer_input`]), and restores it afterward.
+///
+/// The system isn't added by [`crate::EguiPlugin`], add it if your app grabs the cursor (e.g. for camera look)
+/// but you still want to interact with Egui:
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_egui::{input::auto_release_cursor_grab_for_egui_system, EguiPostUpdateSet};
+/// # let mut app = App::new();
+/// app.add_systems(
+///     PostUpdate,
+///     auto_release_cursor_grab_for_egui_system.after(EguiPostUpdateSet::ProcessOutput),
+/// );
+/// ```
+///
+/// If the app changes the grab mode while it's released, the system won't restore the previous one.
+pub fn auto_release_cursor_grab_for_egui_system(
+    egui_wants_input: Res<EguiWantsInput>,
+    mut primary_window: Query<&mut CursorOptions, With<PrimaryWindow>>,
+    mut released_cursor_options: Local<Option<(CursorGrabMode, bool)>>,
+) {
+    let Ok(mut cursor_options) = primary_window.single_mut() else {
+        return;
+    };
+
+    if let Some((grab_mode, visible)) = *released_cursor_options {
+        if cursor_options.grab_mode != CursorGrabMode::None {
+            // The app has grabbed the cursor again.
+            *released_cursor_options = None;
+        } else if !egui_wants_input.wants_any_pointer_input() {
+            cursor_options.grab_mode = grab_mode;
+            cursor_options.visible = visible;
+            *released_cursor_options = None;
+        }
+    } else if cursor_options.grab_mode != CursorGrabMode::None
+        && egui_wants_input.wants_any_pointer_input()
+    {
+        *released_cursor_options = Some((cursor_options.grab_mode, cursor_options.visible));
+        cursor_options.grab_mode = CursorGrabMode::None;
+        cursor_options.visible = true;
+    }
+}
@@ -146,6 +146,12 @@
 //!
 //! - [`bevy-inspector-egui`](https://github.com/jakobhellermann/bevy-inspector-egui)
 
+/// A debug overlay showing per-context pass and repaint info.
+#[cfg(feature = "debug_overlay")]
+pub mod debug_overlay;
+/// `bevy_diagnostic` integration reporting per-context Egui memory usage.
+#[cfg(feature = "diagnostics")]
+pub mod diagnostics;
 /// Helpers for converting Bevy types into Egui ones and vice versa.
 pub mod helpers;
 /// Systems for translating Bevy input events into Egui input.
@@ -155,9 +161,23 @@ pub mod output;
 /// `bevy_picking` integration for Egui.
 #[cfg(feature = "picking")]
 pub mod picking;
+/// Recording and replaying Egui input.
+pub mod record;
 /// Rendering Egui with [`bevy_render`].
 #[cfg(feature = "render")]
 pub mod render;
+/// Capturing screenshots of Egui contexts.
+#[cfg(feature = "render")]
+pub mod screenshot;
+/// Rendering Egui into a CPU-side RGBA buffer, without [`bevy_render`].
+#[cfg(feature = "software_render")]
+pub mod software_render;
+/// Rasterizing SVG images into Egui textures.
+#[cfg(feature = "svg")]
+pub mod svg;
+/// A test harness for UI logic built with `bevy_egui`.
+#[cfg(feature = "test_harness")]
+pub mod test;
 /// Mobile web keyboard input support.
 #[cfg(target_arch = "wasm32")]
 pub mod text_agent;
@@ -181,7 +201,7 @@ use crate::text_agent::{
 use arboard::Clipboard;
 use bevy_app::prelude::*;
 #[cfg(feature = "render")]
-use bevy_asset::{load_internal_asset, AssetEvent, Assets, Handle};
+use bevy_asset::{load_internal_asset, AssetEvent, AssetServer, Assets, Handle};
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{
     prelude::*,
@@ -197,7 +217,7 @@ use bevy_log as log;
 #[cfg(feature = "picking")]
 use bevy_picking::{
     backend::{HitData, PointerHits},
-    pointer::{PointerId, PointerLocation},
+    pointer::{PointerId, PointerLocation, PointerPress},
 };
 #[cfg(feature = "render")]
 use bevy_platform::collections::HashMap;
@@ -360,6 +380,29 @@ pub struct EguiPlugin {
     /// will typically use Bevy UI for the primary game UI, and egui for debug overlays.
     #[cfg(feature = "bevy_ui")]
     pub ui_render_order: UiRenderOrder,
+
+    /// Configures where the Egui pass is placed in the 2D and 3D render graphs.
+    ///
+    /// Defaults to [`EguiPassPlacement::AfterPostProcessing`], i.e. Egui is rendered on top of the
+    /// post-processed image and isn't affected by tonemapping.
+    #[cfg(feature = "render")]
+    pub pass_placement: EguiPassPlacement,
+
+    /// Specializes and compiles the Egui render pipelines during startup (`false` by default).
+    ///
+    /// Otherwise, pipelines are compiled on demand, which may cause a visible hitch when the first Egui
+    /// frame is rendered. Enabling this option queues all the [`render::EguiPipelineKey`] variants
+    /// (HDR and non-HDR targets, both [`EguiColorSpace`] values) as soon as the render app starts.
+    /// The Egui pass doesn't use multisampling, so there's no need to prewarm different sample counts.
+    #[cfg(feature = "render")]
+    pub prewarm_pipelines: bool,
+
+    /// Logs a warning if the `picking` feature is enabled, but [`bevy_picking::PickingPlugin`] isn't added (`true` by default).
+    ///
+    /// Disable it if `picking` is enabled transitively (e.g. by another dependency), but your app doesn't need it.
+    /// Either way, [`capture_pointer_input_system`] is added only if `PickingPlugin` is added before [`EguiPlugin`].
+    #[cfg(feature = "picking")]
+    pub warn_on_missing_picking_plugin: bool,
 }
 
 impl Default for EguiPlugin {
@@ -369,10 +412,31 @@ impl Default for EguiPlugin {
             enable_multipass_for_primary_context: true,
             #[cfg(feature = "bevy_ui")]
             ui_render_order: UiRenderOrder::EguiAboveBevyUi,
+            #[cfg(feature = "render")]
+            pass_placement: EguiPassPlacement::AfterPostProcessing,
+            #[cfg(feature = "render")]
+            prewarm_pipelines: false,
+            #[cfg(feature = "picking")]
+            warn_on_missing_picking_plugin: true,
         }
     }
 }
 
+/// Configures where the Egui pass is placed in the render graph.
+///
+/// See [`EguiPlugin::pass_placement`].
+#[cfg(feature = "render")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EguiPassPlacement {
+    /// Egui is rendered after the main pass, but before tonemapping.
+    ///
+    /// Use this if you render Egui to an HDR camera and want tonemapping
+    /// (and other post-processing effects) to be applied to it.
+    BeforeTonemapping,
+    /// Egui is rendered after post-processing, but before upscaling.
+    AfterPostProcessing,
+}
+
 /// Configures the rendering order between [`egui`] and [`bevy_ui`](Bevy UI).
 ///
 /// See [`EguiPlugin::ui_render_order`].
@@ -383,6 +447,11 @@ pub enum UiRenderOrder {
     EguiAboveBevyUi,
     /// [`bevy_ui`](Bevy UI) UIs are rendered on top of [`egui`].
     BevyUiAboveEgui,
+    /// No render graph edge is added between the [`egui`] and [`bevy_ui`](Bevy UI) passes,
+    /// the order is left for users to configure (e.g. in a custom render graph).
+    ///
+    /// Without an edge, the order is effectively decided at random on every startup.
+    Manual,
 }
 
 /// A resource for storing global plugin settings.
@@ -413,11 +482,46 @@ pub struct EguiGlobalSettings {
     /// Apply `run_if(not(egui_wants_any_pointer_input))` or `run_if(not(egui_wants_any_keyboard_input))` to your systems
     /// that need to be disabled while Egui is using input (see the [`egui_wants_any_pointer_input`], [`egui_wants_any_keyboard_input`] run conditions).
     pub enable_absorb_bevy_input_system: bool,
+    /// Controls which inputs get absorbed by the [`absorb_bevy_input_system`] system.
+    pub absorb_input_settings: AbsorbInputSettings,
     /// Controls whether `bevy_egui` updates [`CursorIcon`], enabled by default.
     ///
     /// If you want to have custom cursor icons in your app, set this to `false` to avoid Egui
     /// overriding the icons.
     pub enable_cursor_icon_updates: bool,
+    /// Controls whether [`run_egui_context_pass_loop_system`] panics if several contexts share
+    /// the same [`EguiMultipassSchedule`], disabled by default.
+    ///
+    /// If disabled, the duplicate contexts are skipped, an error is logged, and [`EguiScheduleConflictEvent`] is sent.
+    /// You may want to enable the strict behavior in development builds.
+    pub panic_on_schedule_conflict: bool,
+    /// Scale factor applied to all Egui contexts (`1.0` by default).
+    ///
+    /// Is multiplied by [`EguiContextSettings::scale_factor`] of each context, which makes it
+    /// convenient for implementing an accessibility "UI zoom" setting.
+    pub global_scale_factor: f32,
+    /// A shortcut that moves [`FocusedNonWindowEguiContext`] to the next context listed in
+    /// [`EguiFocusCycleContexts`] (`None` by default).
+    ///
+    /// If the shortcut doesn't include Shift, pressing it with Shift moves the focus to the previous context.
+    /// The shortcut itself isn't forwarded to Egui. See [`cycle_focused_non_window_context_system`].
+    ///
+    /// ```rust
+    /// # use bevy_egui::{egui, EguiGlobalSettings};
+    /// let settings = EguiGlobalSettings {
+    ///     focus_cycle_shortcut: Some(egui::KeyboardShortcut::new(
+    ///         egui::Modifiers::CTRL,
+    ///         egui::Key::Tab,
+    ///     )),
+    ///     ..Default::default()
+    /// };
+    /// ```
+    #[reflect(ignore)]
+    pub focus_cycle_shortcut: Option<egui::KeyboardShortcut>,
+    /// Despawns contexts when their window is closed, disabled by default.
+    ///
+    /// [`EguiContextWindowClosedEvent`] is sent either way.
+    pub despawn_contexts_on_window_close: bool,
 }
 
 impl Default for EguiGlobalSettings {
@@ -427,7 +531,43 @@ impl Default for EguiGlobalSettings {
             enable_focused_non_window_context_updates: true,
             input_system_settings: EguiInputSystemSettings::default(),
             enable_absorb_bevy_input_system: false,
+            absorb_input_settings: AbsorbInputSettings::default(),
             enable_cursor_icon_updates: true,
+            panic_on_schedule_conflict: false,
+            global_scale_factor: 1.0,
+            focus_cycle_shortcut: None,
+            despawn_contexts_on_window_close: false,
+        }
+    }
+}
+
+/// Controls which inputs get absorbed by the [`absorb_bevy_input_system`] system,
+/// see [`EguiGlobalSettings::absorb_input_settings`].
+#[derive(Clone, Debug, Reflect, PartialEq, Eq)]
+pub struct AbsorbInputSettings {
+    /// Absorb keyboard input if Egui wants keyboard input (enabled by default).
+    pub keyboard: bool,
+    /// Absorb mouse button input if Egui wants pointer input (enabled by default).
+    pub mouse_buttons: bool,
+    /// Absorb mouse wheel input if Egui wants scroll input (enabled by default).
+    pub mouse_wheel: bool,
+    /// Absorb mouse motion input if Egui wants pointer input (disabled by default).
+    pub mouse_motion: bool,
+    /// Absorb touch input if Egui wants pointer input (disabled by default).
+    pub touch: bool,
+    /// Absorb gamepad input if Egui wants keyboard input (disabled by default).
+    pub gamepad: bool,
+}
+
+impl Default for AbsorbInputSettings {
+    fn default() -> Self {
+        Self {
+            keyboard: true,
+            mouse_buttons: true,
+            mouse_wheel: true,
+            mouse_motion: false,
+            touch: false,
+            gamepad: false,
         }
     }
 }
@@ -440,6 +580,15 @@ pub struct EnableMultipassForPrimaryContext;
 #[derive(Clone, Debug, Component, Reflect)]
 pub struct EguiContextSettings {
     /// If set to `true`, a user is expected to call [`egui::Context::run`] or [`egui::Context::begin_pass`] and [`egui::Context::end_pass`] manually.
+    ///
+    /// The pass output has to be written to [`EguiFullOutput`]. Frames without a pass output don't overwrite
+    /// [`EguiRenderOutput`], which can be set directly instead.
+    ///
+    /// Such contexts are skipped by all the systems that consume [`EguiInput`] ([`begin_pass_system`] and
+    /// [`run_egui_context_pass_loop_system`]), so input events keep accumulating in the component until you take them
+    /// (e.g. with [`egui::RawInput::take`]). This lets you decide which of your passes receive the input,
+    /// but make sure to take it eventually. Input systems still write events, and update the screen rect,
+    /// time, modifiers and focus.
     pub run_manually: bool,
     /// Global scale factor for Egui widgets (`1.0` by default).
     ///
@@ -459,9 +608,21 @@ pub struct EguiContextSettings {
     /// If not specified, `_self` will be used. Only matters in a web browser.
     #[cfg(feature = "open_url")]
     pub default_open_url_target: Option<String>,
+    /// Controls whether hyperlinks clicked in the context are opened with the default browser, enabled by default.
+    ///
+    /// Set this to `false` if you want to handle links in-app: the [`egui::OutputCommand::OpenUrl`] commands
+    /// can be read from [`EguiOutput::platform_output`].
+    #[cfg(feature = "open_url")]
+    pub open_url_externally: bool,
     /// Controls if Egui should capture pointer input when using [`bevy_picking`] (i.e. suppress `bevy_picking` events when a pointer is over an Egui window).
     #[cfg(feature = "picking")]
     pub capture_pointer_input: bool,
+    /// Controls whether input is forwarded to the context, enabled by default.
+    ///
+    /// Setting this to `false` makes all the `write_*` input systems skip the context
+    /// (regardless of [`EguiContextSettings::input_system_settings`]), which is handy for pausing
+    /// a background context while a modal UI is shown.
+    pub input_enabled: bool,
     /// Controls running of the input systems.
     pub input_system_settings: EguiInputSystemSettings,
     /// Controls whether `bevy_egui` updates [`CursorIcon`], enabled by default.
@@ -469,6 +630,287 @@ pub struct EguiContextSettings {
     /// If you want to have custom cursor icons in your app, set this to `false` to avoid Egui
     /// overriding the icons.
     pub enable_cursor_icon_updates: bool,
+    /// Controls whether the context reports wanting scroll input only over scroll areas (disabled by default),
+    /// see [`EguiWantsInput::wants_scroll`] for the details.
+    ///
+    /// By default, the context wants scroll input whenever it wants pointer input (e.g. when a pointer
+    /// is over any Egui panel). Enable this if you want scrolling over empty panel space to reach
+    /// your game systems (see [`EguiWantsInput::wants_scroll`]).
+    pub capture_scroll_only_over_scroll_areas: bool,
+    /// Controls whether hovering an Egui area counts as wanting pointer input (enabled by default).
+    ///
+    /// If disabled, [`EguiWantsInput::is_pointer_over_area`] and [`EguiWantsInput::wants_pointer_input`]
+    /// are `false` unless Egui is actually using the pointer (e.g. a widget is being clicked or dragged),
+    /// which makes panels "hover-transparent" for [`EguiWantsInput::wants_any_pointer_input`] and
+    /// [`absorb_bevy_input_system`]. Note that clicks still reach Egui widgets; game systems may need
+    /// to check [`EguiWantsInput::is_using_pointer`] to avoid reacting to the same click.
+    pub wants_pointer_includes_hover: bool,
+    /// Overrides [`egui::RawInput::max_texture_side`] (`None` by default).
+    ///
+    /// If not specified, the value is read from the render device limits. Egui uses it to limit
+    /// the size of the font atlas, so you might want to lower it if your target GPU can't allocate
+    /// textures of the reported size.
+    pub max_texture_side: Option<usize>,
+    /// Controls the color space in which Egui vertex colors are multiplied with textures
+    /// (defaults to [`EguiColorSpace::Srgb`]).
+    #[cfg(feature = "render")]
+    pub color_space: EguiColorSpace,
+    /// Controls whether the Egui pass is skipped for frames in which the context has no shapes to paint
+    /// (disabled by default).
+    ///
+    /// Enabling this avoids binding pipelines and resources when, for example, all the panels are hidden,
+    /// which may be useful for battery-sensitive apps. Texture updates are still applied.
+    ///
+    /// Note that the render target isn't touched by the Egui pass in skipped frames. If the camera doesn't clear
+    /// its target (e.g. [`bevy_render::camera::ClearColorConfig::None`] when rendering to an image),
+    /// the target keeps the content of the last rendered frame.
+    #[cfg(feature = "render")]
+    pub skip_empty_render_output: bool,
+    /// Color that the render target is filled with by the Egui pass, if the context renders
+    /// to an [`Image`] (`None` by default).
+    ///
+    /// Unlike [`egui::Visuals::panel_fill`], the color covers the whole target, including the areas that Egui didn't paint,
+    /// which is useful for compositing Egui onto meshes. Contexts of [`EguiImageTarget`] entities clear the image
+    /// with the color. For contexts of cameras rendering to an image, the color is painted over the camera viewport
+    /// before the Egui output, so it covers what the camera rendered in that viewport, but not other cameras' viewports.
+    #[cfg(feature = "render")]
+    #[reflect(ignore)]
+    pub render_background: Option<egui::Color32>,
+    /// Flips the Egui output vertically (`false` by default).
+    ///
+    /// Textures produced by contexts rendering to an [`Image`] follow the usual convention where the first row
+    /// of the texture is the top of the UI. If a mesh samples the texture with UVs where `v = 0` is at the bottom
+    /// (e.g. some meshes imported from other tools), the UI appears upside-down, and enabling this setting
+    /// is an alternative to flipping the UVs. Bevy's built-in meshes (as in the `render_egui_to_image` example)
+    /// don't need it. Note that [`egui::PaintCallback`]s aren't flipped.
+    #[cfg(feature = "render")]
+    pub flip_y: bool,
+    /// Pins the layout of the context to a fixed logical size (`None` by default), e.g. `320×180` for pixel-perfect retro UIs.
+    ///
+    /// Unlike [`EguiContextSettings::scale_factor`], the screen size of the context stays the same regardless
+    /// of the viewport size. Egui lays out and rasterizes the UI at one pixel per point, and the output is upscaled
+    /// (keeping the aspect ratio, with the UI anchored to the top left corner of the viewport) with nearest filtering
+    /// of Egui-managed textures. Consider disabling [`egui::epaint::TessellationOptions::feathering`]
+    /// in [`EguiContextSettings::tessellation_options`] to get sharp edges.
+    #[cfg(feature = "render")]
+    pub fixed_logical_size: Option<bevy_math::UVec2>,
+    /// Options used for tessellating Egui shapes into paint jobs (match Egui defaults).
+    ///
+    /// Disabling [`egui::epaint::TessellationOptions::feathering`] may help if you want pixel-exact output,
+    /// increasing [`egui::epaint::TessellationOptions::bezier_tolerance`] may improve performance.
+    #[reflect(ignore)]
+    pub tessellation_options: egui::epaint::TessellationOptions,
+    /// Controls whether IME events are forwarded to the context, enabled by default.
+    ///
+    /// If set to `false`, the context behaves as if IME was never enabled: [`bevy_window::Ime`] events are ignored
+    /// and `bevy_egui` doesn't call `set_ime_allowed` for the context. Unlike
+    /// [`EguiInputSystemSettings::run_write_ime_events_system`], this setting is respected by all the IME-related systems.
+    pub ime_enabled: bool,
+    /// Controls whether scrolling with Ctrl (Cmd on macOS) pressed zooms the context, enabled by default to match Egui.
+    ///
+    /// Egui zooms when the modifiers of [`egui::Event::MouseWheel`] match [`egui::InputOptions::zoom_modifier`].
+    /// Disabling this setting clears the modifier (see [`update_scroll_zoom_modifier_system`]), so that such scrolling
+    /// scrolls instead. Enabling it again restores the modifier the context had before (or the one
+    /// from [`EguiContextOptions::input_options`], if they changed in the meantime).
+    pub allow_ctrl_scroll_zoom: bool,
+    /// Controls whether [`MouseWheel`](bevy_input::mouse::MouseWheel) events received in the same frame are coalesced into
+    /// a single [`egui::Event::MouseWheel`] per scroll unit, disabled by default.
+    ///
+    /// Trackpads and high-resolution wheels may send many tiny scroll events per frame, enabling this setting
+    /// makes Egui receive their sum instead.
+    pub coalesce_mouse_wheel_events: bool,
+    /// Controls whether [`egui::Event::MouseMoved`] is sent along with [`egui::Event::PointerMoved`] for window contexts,
+    /// disabled by default.
+    ///
+    /// The delta is the change of [`EguiContextMousePosition`] in points, which can be useful for widgets
+    /// that use relative motion (e.g. 3D gizmos). Enabling it doubles the number of pointer events.
+    pub send_mouse_moved_events: bool,
+    /// Configures key repeat synthesized by `bevy_egui` (`None` by default).
+    ///
+    /// If `None`, repeat events generated by the OS are forwarded to Egui. Some platforms don't generate
+    /// repeat events, which makes holding arrow keys or backspace in text fields move or delete only once.
+    /// If set, OS repeat events are ignored, and `bevy_egui` repeats the last held key on its own.
+    pub key_repeat: Option<EguiKeyRepeatSettings>,
+    /// **Experimental.** Extrapolates the emulated pointer position of a moving touch by the specified time (in seconds),
+    /// based on the touch velocity (`None` by default).
+    ///
+    /// On touch devices with high input latency, this can make drawing (e.g. with [`egui::Painter`]) follow
+    /// the finger more closely, at the cost of overshooting when the touch changes direction abruptly.
+    /// A good starting point is the duration of a frame or two (e.g. `Some(0.016)`).
+    pub pointer_latency_compensation: Option<f32>,
+    /// Configures scroll momentum synthesized after flick-scrolling with a touch (`None` by default).
+    ///
+    /// If set, when the touch that emulates the pointer ends, `bevy_egui` keeps sending decaying
+    /// [`egui::Event::MouseWheel`] events based on the velocity of the touch, approximating native momentum scrolling.
+    /// The pointer stays at the released position until the momentum fades out, or until a new touch starts.
+    pub touch_inertia: Option<EguiTouchInertiaSettings>,
+    /// Calibrates the force of [`egui::Event::Touch`] events (`None` by default, i.e. the force is passed through),
+    /// which can help with pressure-sensitive drawing on devices that report unusual force ranges.
+    pub touch_force: Option<EguiTouchForceSettings>,
+    /// Controls whether the Escape key is consumed by Egui or forwarded to the game
+    /// ([`EguiEscapeBehavior::Egui`] by default).
+    ///
+    /// Games often use Escape to open a pause menu, while Egui uses it to close popups and unfocus text fields.
+    pub escape_behavior: EguiEscapeBehavior,
+    /// Keys that are never forwarded to Egui (empty by default), neither as [`egui::Event::Key`] nor as [`egui::Event::Text`].
+    ///
+    /// A key is blocked if either its logical or physical key matches. Use this to keep game controls
+    /// (e.g. Space for jumping) working regardless of which Egui widget has focus:
+    ///
+    /// ```rust
+    /// # use bevy_egui::{egui, EguiContextSettings};
+    /// let settings = EguiContextSettings {
+    ///     blocked_keys: vec![egui::Key::Space],
+    ///     ..Default::default()
+    /// };
+    /// ```
+    #[reflect(ignore)]
+    pub blocked_keys: Vec<egui::Key>,
+    /// The lower bound of [`egui::Context::pixels_per_point`] (`0.1` by default).
+    ///
+    /// The effective scale factor (see [`EguiContextComputedScaleFactor`]) is clamped to
+    /// `min_pixels_per_point..=max_pixels_per_point`, which guards against pathological DPI values or a misconfigured
+    /// [`EguiContextSettings::scale_factor`] that would make Egui build huge or unreadably small font atlases.
+    ///
+    /// Must be positive and not greater than [`EguiContextSettings::max_pixels_per_point`], otherwise both bounds
+    /// fall back to their defaults (and a warning is logged).
+    pub min_pixels_per_point: f32,
+    /// The upper bound of [`egui::Context::pixels_per_point`] (`8.0` by default), see [`EguiContextSettings::min_pixels_per_point`].
+    ///
+    /// Must be finite and not less than [`EguiContextSettings::min_pixels_per_point`], otherwise both bounds
+    /// fall back to their defaults (and a warning is logged).
+    pub max_pixels_per_point: f32,
+    /// Decides which context claims the pointer if several contexts rendering to the same window
+    /// want it at the same time (e.g. stacked cameras with overlapping UIs), `0` by default.
+    ///
+    /// Contexts with a lower priority than another claiming context of the same window don't capture
+    /// the pointer for `bevy_picking` and have their pointer-related [`EguiWantsInput`] flags cleared.
+    /// Contexts with equal priorities all keep claiming the pointer.
+    pub pointer_capture_priority: i32,
+    /// Controls [`egui::Context::set_embed_viewports`], enabled by default.
+    ///
+    /// `bevy_egui` doesn't create native windows for Egui viewports, so with this setting enabled,
+    /// [`egui::Context::show_viewport_immediate`] and [`egui::Context::show_viewport_deferred`] show viewports
+    /// as [`egui::Window`]s embedded into the context (i.e. drawn within the window or image the context renders to).
+    /// Disable it only if you create native windows for viewports on your own.
+    pub embed_viewports: bool,
+    /// Mouse buttons that the context is allowed to capture (all the buttons by default).
+    ///
+    /// Buttons missing from the list aren't absorbed by [`absorb_bevy_input_system`] even if the pointer
+    /// is over the context, e.g. remove [`egui::PointerButton::Secondary`] to let right-clicks open game context menus.
+    /// Egui still receives the buttons. With `capture_pointer_input`, the context doesn't capture `bevy_picking`
+    /// pointers while buttons missing from the list are pressed, so that the presses reach the entities below.
+    ///
+    /// Both [`ButtonInput<MouseButton>`](bevy_input::ButtonInput) and `Events<MouseButtonInput>` are filtered per button,
+    /// see [`absorb_bevy_input_system`] for the details.
+    #[reflect(ignore)]
+    pub capture_buttons: Vec<egui::PointerButton>,
+}
+
+/// Configures synthesized key repeat, see [`EguiContextSettings::key_repeat`].
+#[derive(Clone, Copy, Debug, Reflect, PartialEq)]
+pub struct EguiKeyRepeatSettings {
+    /// Delay before the first repeat event (in seconds).
+    pub initial_delay: f32,
+    /// Interval between repeat events (in seconds).
+    pub interval: f32,
+}
+
+impl Default for EguiKeyRepeatSettings {
+    fn default() -> Self {
+        Self {
+            initial_delay: 0.5,
+            interval: 1.0 / 30.0,
+        }
+    }
+}
+
+/// Configures synthesized touch scroll momentum, see [`EguiContextSettings::touch_inertia`].
+#[derive(Clone, Copy, Debug, Reflect, PartialEq)]
+pub struct EguiTouchInertiaSettings {
+    /// How quickly the momentum decays: every frame, the velocity is multiplied by `exp(-friction * delta_secs)`.
+    pub friction: f32,
+    /// The velocity (in points per second) below which the momentum stops.
+    pub min_velocity: f32,
+}
+
+impl Default for EguiTouchInertiaSettings {
+    fn default() -> Self {
+        Self {
+            friction: 4.0,
+            min_velocity: 20.0,
+        }
+    }
+}
+
+/// Maps touch force reported by a device to the force Egui receives, see [`EguiContextSettings::touch_force`].
+///
+/// The force (normalized by Bevy to the `0.0..=1.0` range if the device reports calibrated values)
+/// is remapped from `min..=max` to `0.0..=1.0`, clamped, and then passed through `curve`.
+#[derive(Clone, Copy, Debug, Reflect, PartialEq)]
+pub struct EguiTouchForceSettings {
+    /// The force that maps to `0.0`.
+    pub min: f32,
+    /// The force that maps to `1.0`.
+    pub max: f32,
+    /// A response curve applied to the remapped force, e.g. `|force| force.powf(0.5)`
+    /// to make light strokes more pronounced.
+    #[reflect(ignore)]
+    pub curve: Option<fn(f32) -> f32>,
+}
+
+impl Default for EguiTouchForceSettings {
+    fn default() -> Self {
+        Self {
+            min: 0.0,
+            max: 1.0,
+            curve: None,
+        }
+    }
+}
+
+impl EguiTouchForceSettings {
+    /// Maps the force reported by a device.
+    pub fn map_force(&self, force: f32) -> f32 {
+        let range = self.max - self.min;
+        let force = if range > 0.0 {
+            ((force - self.min) / range).clamp(0.0, 1.0)
+        } else {
+            force
+        };
+        match self.curve {
+            Some(curve) => curve(force),
+            None => force,
+        }
+    }
+}
+
+/// Controls who receives the Escape key, see [`EguiContextSettings::escape_behavior`].
+///
+/// Note that when Egui wants keyboard input and Escape isn't absorbed, [`absorb_bevy_input_system`] (if enabled)
+/// resets all the other keys in [`ButtonInput<KeyCode>`](bevy_input::ButtonInput) and keeps only the Escape
+/// [`bevy_input::keyboard::KeyboardInput`] events.
+#[derive(Clone, Copy, Debug, Default, Reflect, PartialEq, Eq)]
+pub enum EguiEscapeBehavior {
+    /// Escape is always sent to Egui, and is absorbed along with other keys if Egui wants keyboard input.
+    #[default]
+    Egui,
+    /// Escape is never sent to Egui and is never absorbed, so it always reaches the game.
+    Game,
+    /// Escape is sent to Egui (and absorbed) only if a popup is open or a widget has keyboard focus
+    /// (see [`EguiWantsInput::wants_any_keyboard_input`]), otherwise it reaches only the game.
+    EguiIfFocused,
+}
+
+impl EguiEscapeBehavior {
+    /// Returns `true` if Escape should be sent to a context, based on the context's [`EguiWantsInput`].
+    pub fn forwards_to_egui(&self, context_wants_input: &EguiWantsInput) -> bool {
+        match self {
+            Self::Egui => true,
+            Self::Game => false,
+            Self::EguiIfFocused => context_wants_input.wants_any_keyboard_input(),
+        }
+    }
 }
 
 // Just to keep the PartialEq
@@ -489,14 +931,97 @@ impl Default for EguiContextSettings {
             scale_factor: 1.0,
             #[cfg(feature = "open_url")]
             default_open_url_target: None,
+            #[cfg(feature = "open_url")]
+            open_url_externally: true,
             #[cfg(feature = "picking")]
             capture_pointer_input: true,
+            input_enabled: true,
             input_system_settings: EguiInputSystemSettings::default(),
             enable_cursor_icon_updates: true,
+            capture_scroll_only_over_scroll_areas: false,
+            wants_pointer_includes_hover: true,
+            max_texture_side: None,
+            #[cfg(feature = "render")]
+            color_space: EguiColorSpace::default(),
+            #[cfg(feature = "render")]
+            skip_empty_render_output: false,
+            #[cfg(feature = "render")]
+            render_background: None,
+            #[cfg(feature = "render")]
+            flip_y: false,
+            #[cfg(feature = "render")]
+            fixed_logical_size: None,
+            tessellation_options: egui::epaint::TessellationOptions::default(),
+            ime_enabled: true,
+            allow_ctrl_scroll_zoom: true,
+            coalesce_mouse_wheel_events: false,
+            send_mouse_moved_events: false,
+            key_repeat: None,
+            pointer_latency_compensation: None,
+            touch_inertia: None,
+            touch_force: None,
+            escape_behavior: EguiEscapeBehavior::Egui,
+            blocked_keys: Vec::new(),
+            min_pixels_per_point: 0.1,
+            max_pixels_per_point: 8.0,
+            pointer_capture_priority: 0,
+            embed_viewports: true,
+            capture_buttons: vec![
+                egui::PointerButton::Primary,
+                egui::PointerButton::Secondary,
+                egui::PointerButton::Middle,
+                egui::PointerButton::Extra1,
+                egui::PointerButton::Extra2,
+            ],
         }
     }
 }
 
+impl EguiContextSettings {
+    /// Returns `true` if the context is allowed to capture a mouse button, see [`EguiContextSettings::capture_buttons`].
+    ///
+    /// Buttons that have no [`egui::PointerButton`] counterpart are always captured.
+    pub fn captures_button(&self, button: bevy_input::mouse::MouseButton) -> bool {
+        helpers::bevy_to_egui_pointer_button(button)
+            .map_or(true, |button| self.capture_buttons.contains(&button))
+    }
+
+    /// Clamps a scale factor to [`EguiContextSettings::min_pixels_per_point`] and [`EguiContextSettings::max_pixels_per_point`].
+    ///
+    /// If the bounds are invalid (i.e. not `0.0 < min_pixels_per_point <= max_pixels_per_point < f32::INFINITY`),
+    /// the default ones are used instead.
+    pub fn clamp_pixels_per_point(&self, pixels_per_point: f32) -> f32 {
+        let (min, max) = if 0.0 < self.min_pixels_per_point
+            && self.min_pixels_per_point <= self.max_pixels_per_point
+            && self.max_pixels_per_point.is_finite()
+        {
+            (self.min_pixels_per_point, self.max_pixels_per_point)
+        } else {
+            log::warn_once!(
+                "Invalid pixels per point bounds ({}..={}), falling back to the defaults",
+                self.min_pixels_per_point,
+                self.max_pixels_per_point
+            );
+            let default = Self::default();
+            (default.min_pixels_per_point, default.max_pixels_per_point)
+        };
+        pixels_per_point.max(min).min(max)
+    }
+}
+
+/// Color space in which Egui vertex colors are blended with textures, see [`EguiContextSettings::color_space`].
+#[cfg(feature = "render")]
+#[derive(Clone, Copy, Debug, Default, Reflect, PartialEq, Eq, Hash)]
+pub enum EguiColorSpace {
+    /// Colors are multiplied in the sRGB (gamma) space, the same way other Egui integrations do it.
+    ///
+    /// This is the only way to get text to look right, according to Egui.
+    #[default]
+    Srgb,
+    /// Vertex colors are converted into the linear space before being multiplied with textures.
+    Linear,
+}
+
 #[derive(Clone, Debug, Reflect, PartialEq, Eq)]
 /// All the systems are enabled by default. These settings exist within both [`EguiGlobalSettings`] and [`EguiContextSettings`].
 pub struct EguiInputSystemSettings {
@@ -512,6 +1037,8 @@ pub struct EguiInputSystemSettings {
     pub run_write_non_window_pointer_moved_events_system: bool,
     /// Controls running of the [`write_mouse_wheel_events_system`] system.
     pub run_write_mouse_wheel_events_system: bool,
+    /// Controls running of the [`write_zoom_gesture_events_system`] system.
+    pub run_write_zoom_gesture_events_system: bool,
     /// Controls running of the [`write_non_window_touch_events_system`] system.
     pub run_write_non_window_touch_events_system: bool,
     /// Controls running of the [`write_keyboard_input_events_system`] system.
@@ -537,6 +1064,7 @@ impl Default for EguiInputSystemSettings {
             run_write_window_touch_events_system: true,
             run_write_non_window_pointer_moved_events_system: true,
             run_write_mouse_wheel_events_system: true,
+            run_write_zoom_gesture_events_system: true,
             run_write_non_window_touch_events_system: true,
             run_write_keyboard_input_events_system: true,
             run_write_ime_events_system: true,
@@ -559,8 +1087,20 @@ pub struct EguiPrimaryContextPass;
 #[require(EguiMultipassSchedule::new(EguiPrimaryContextPass))]
 pub struct PrimaryEguiContext;
 
+/// A marker component for a camera that should host the primary Egui context when it's created automatically
+/// (see [`EguiGlobalSettings::auto_create_primary_context`]).
+///
+/// Without it, the primary context is attached to the first camera found, which can depend on the spawn order
+/// if several cameras are spawned at once. The marker only affects cameras spawned in the same frame as
+/// (or before) the first camera, as the primary context is created only once.
+#[cfg(feature = "render")]
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct PreferredPrimaryEguiCamera;
+
 /// Add this component to your additional Egui contexts (e.g. when rendering to a new window or an image),
 /// to enable multi-pass support. Note that each Egui context running in the multi-pass mode must use a unique schedule.
+///
+/// The number of passes per frame is limited by [`EguiContextOptions::max_passes`].
 #[derive(Component, Clone)]
 #[require(EguiContext)]
 pub struct EguiMultipassSchedule(pub InternedScheduleLabel);
@@ -572,13 +1112,161 @@ impl EguiMultipassSchedule {
     }
 }
 
+/// Add this component to an entity without a camera to create an offscreen Egui context.
+///
+/// Offscreen contexts have an explicit screen size and scale factor and don't produce any paint jobs
+/// (their textures are still kept up to date).
+/// They can be useful for laying out or measuring text before showing a real UI.
+/// Like any other context, an offscreen context needs a unique [`EguiMultipassSchedule`] to run UI systems:
+///
+/// ```rust
+/// # use bevy::{ecs::schedule::ScheduleLabel, prelude::*};
+/// # use bevy_egui::{EguiMultipassSchedule, EguiOffscreenContext};
+/// #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+/// pub struct OffscreenContextPass;
+///
+/// fn setup_system(mut commands: Commands) {
+///     commands.spawn((
+///         EguiOffscreenContext::new(Vec2::new(800.0, 600.0)).with_scale_factor(2.0),
+///         EguiMultipassSchedule::new(OffscreenContextPass),
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone, Debug, PartialEq)]
+#[require(EguiContext)]
+pub struct EguiOffscreenContext {
+    /// Logical size of the context screen.
+    pub size: bevy_math::Vec2,
+    /// Scale factor of the context, is multiplied by [`EguiContextSettings::scale_factor`] (`1.0` by default).
+    pub scale_factor: f32,
+}
+
+impl EguiOffscreenContext {
+    /// Constructs the component from a logical screen size.
+    pub fn new(size: bevy_math::Vec2) -> Self {
+        Self {
+            size,
+            scale_factor: 1.0,
+        }
+    }
+
+    /// Sets the scale factor of the context.
+    pub fn with_scale_factor(mut self, scale_factor: f32) -> Self {
+        self.scale_factor = scale_factor;
+        self
+    }
+}
+
+/// Add this component to an entity without a camera to render an Egui context directly into an existing image.
+///
+/// Unlike rendering to an image via a camera with [`bevy_render::camera::RenderTarget::Image`],
+/// the context isn't tied to the camera extraction and can be rendered on demand:
+/// the render output is extracted only when [`EguiImageTarget::continuous`] is enabled,
+/// or once after [`EguiImageTarget::request_render`] is called. Image targets are rendered before cameras,
+/// so cameras that display the image get the updated contents in the same frame.
+///
+/// The size of the context screen equals the image size divided by the scale factor.
+/// The image must have the [`wgpu_types::TextureUsages::RENDER_ATTACHMENT`] usage and either
+/// the [`wgpu_types::TextureFormat::Rgba8UnormSrgb`] or the [`wgpu_types::TextureFormat::Rgba16Float`] format.
+/// Like any other context, the context needs a unique [`EguiMultipassSchedule`] to run UI systems:
+///
+/// ```rust
+/// # use bevy::{ecs::schedule::ScheduleLabel, prelude::*};
+/// # use bevy_egui::{EguiImageTarget, EguiMultipassSchedule};
+/// #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+/// pub struct ImageContextPass;
+///
+/// fn setup_system(mut commands: Commands, image: Res<MyImage>) {
+///     commands.spawn((
+///         EguiImageTarget::new(image.0.clone()),
+///         EguiMultipassSchedule::new(ImageContextPass),
+///     ));
+/// }
+///
+/// fn redraw_system(mut image_targets: Query<&mut EguiImageTarget>) {
+///     for mut image_target in image_targets.iter_mut() {
+///         image_target.request_render();
+///     }
+/// }
+/// # #[derive(Resource)]
+/// # struct MyImage(Handle<Image>);
+/// ```
+#[cfg(feature = "render")]
+#[derive(Component, Clone, Debug, PartialEq)]
+#[require(EguiContext)]
+pub struct EguiImageTarget {
+    /// The image Egui is rendered into.
+    pub image: Handle<Image>,
+    /// Scale factor of the context, is multiplied by [`EguiContextSettings::scale_factor`] (`1.0` by default).
+    pub scale_factor: f32,
+    /// Render the context every frame, `false` by default.
+    pub continuous: bool,
+    /// Is set by [`EguiImageTarget::request_render`] and reset once the render output is extracted.
+    pub render_requested: bool,
+}
+
+#[cfg(feature = "render")]
+impl EguiImageTarget {
+    /// Constructs the component from an image handle.
+    pub fn new(image: Handle<Image>) -> Self {
+        Self {
+            image,
+            scale_factor: 1.0,
+            continuous: false,
+            render_requested: false,
+        }
+    }
+
+    /// Sets the scale factor of the context.
+    pub fn with_scale_factor(mut self, scale_factor: f32) -> Self {
+        self.scale_factor = scale_factor;
+        self
+    }
+
+    /// Makes the context render every frame.
+    pub fn continuous(mut self) -> Self {
+        self.continuous = true;
+        self
+    }
+
+    /// Renders the current output of the context into the image at the end of this frame.
+    pub fn request_render(&mut self) {
+        self.render_requested = true;
+    }
+}
+
 /// Is used for storing Egui context input.
 ///
-/// It gets reset during the [`crate::EguiInputSet::WriteEguiEvents`] system set.
+/// Events are written during the [`crate::EguiInputSet::WriteEguiEvents`] system set, and the input is taken
+/// (which resets it) when a pass begins: in [`begin_pass_system`] or [`run_egui_context_pass_loop_system`].
+/// For contexts with [`EguiContextSettings::run_manually`] enabled, the input is never taken by `bevy_egui`.
 #[derive(Component, Clone, Debug, Default, Deref, DerefMut)]
 pub struct EguiInput(pub egui::RawInput);
 
+impl EguiInput {
+    /// Drops input queued for the context, e.g. to avoid a click "leaking" into a dialog
+    /// that is opened in response to the same click.
+    ///
+    /// Clears events (except for pointer button releases, to avoid Egui considering buttons stuck),
+    /// hovered and dropped files, and resets modifiers.
+    ///
+    /// Input is collected in [`EguiPreUpdateSet::ProcessInput`] and consumed when the pass begins:
+    /// - in [`EguiPreUpdateSet::BeginPass`] for contexts without [`EguiMultipassSchedule`],
+    ///   so you need to call this function from a system scheduled between these sets,
+    /// - in [`run_egui_context_pass_loop_system`] (in [`PostUpdate`]) for contexts with [`EguiMultipassSchedule`],
+    ///   so calling it from [`Update`](bevy_app::Update) systems works as well.
+    pub fn clear_queued_input(&mut self) {
+        self.events
+            .retain(|event| matches!(event, egui::Event::PointerButton { pressed: false, .. }));
+        self.hovered_files.clear();
+        self.dropped_files.clear();
+        self.modifiers = egui::Modifiers::default();
+    }
+}
+
 /// Intermediate output buffer generated on an Egui pass end and consumed by the [`process_output_system`] system.
+///
+/// Systems in the [`EguiPostUpdateSet::TransformOutput`] set can modify the output before it's consumed.
 #[derive(Component, Clone, Default, Deref, DerefMut)]
 pub struct EguiFullOutput(pub Option<egui::FullOutput>);
 
@@ -590,11 +1278,50 @@ pub struct EguiFullOutput(pub Option<egui::FullOutput>);
 pub struct EguiClipboard {
     #[cfg(not(target_arch = "wasm32"))]
     clipboard: thread_local::ThreadLocal<Option<RefCell<Clipboard>>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    last_event_kind: Option<EguiClipboardContentKind>,
     #[cfg(target_arch = "wasm32")]
     clipboard: web_clipboard::WebClipboard,
 }
 
+/// An in-memory clipboard scoped to an Egui context, insert it into a context entity to isolate copy-paste
+/// of the context from other contexts and the OS clipboard (e.g. for sandboxed UIs).
+///
+/// If the component is present, copy, cut and paste shortcuts of the context use it instead of [`EguiClipboard`]
+/// (even if the `manage_clipboard` feature is disabled). Only text is supported, copied images are dropped.
+/// On web, browser copy, cut and paste events are ignored for such contexts.
+#[derive(Component, Clone, Debug, Default)]
+pub struct EguiContextClipboard {
+    /// The copied text.
+    pub text: Option<String>,
+}
+
+/// Kind of clipboard contents, see [`EguiClipboard::last_event_kind`].
+#[cfg(all(feature = "manage_clipboard", not(target_os = "android")))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EguiClipboardContentKind {
+    /// The clipboard contains text.
+    Text,
+    /// The clipboard contains an image (and no text).
+    Image,
+    /// The clipboard is empty or contains data of an unsupported format.
+    Empty,
+}
+
 /// Is used for storing Egui shapes and textures delta.
+///
+/// The component can also be written directly to render pre-tessellated primitives (e.g. a cached overlay)
+/// with the `bevy_egui` pipeline, without running an Egui pass. For that, set
+/// [`EguiContextSettings::run_manually`] to `true` and don't run the context: [`process_output_system`]
+/// only overwrites the component when the context has produced a pass output ([`EguiFullOutput`]).
+///
+/// Note that:
+/// - The output is moved into the render world every frame, so it has to be set every frame
+///   (e.g. by cloning the cached primitives in a system running after [`EguiPostUpdateSet::ProcessOutput`]).
+/// - Primitives are rendered with [`EguiContextComputedScaleFactor`] of the context, so they should be tessellated
+///   with the same `pixels_per_point`.
+/// - [`egui::TextureId::Managed`] ids refer to the textures of the context, use [`EguiUserTextures`]
+///   (i.e. [`egui::TextureId::User`]) for your own images.
 #[derive(Component, Clone, Default, Debug)]
 pub struct EguiRenderOutput {
     /// Pairs of rectangles and paint commands.
@@ -618,6 +1345,14 @@ impl EguiRenderOutput {
 pub struct EguiOutput {
     /// The field gets updated during [`process_output_system`] (in the [`EguiPostUpdateSet::ProcessOutput`] set, belonging to [`PostUpdate`]).
     pub platform_output: egui::PlatformOutput,
+    /// The delay after which Egui wants to be repainted (e.g. because an animation is in progress),
+    /// `None` if Egui doesn't need a repaint. `Some(Duration::ZERO)` means that a repaint is requested immediately.
+    ///
+    /// Reactive apps (e.g. ones using `WinitSettings::desktop_app`) can read it to schedule the next update,
+    /// as it's `None` when Egui can sleep until the next input event.
+    ///
+    /// The field gets updated during [`process_output_system`] as well.
+    pub repaint_delay: Option<std::time::Duration>,
 }
 
 /// A component for storing `bevy_egui` context.
@@ -627,10 +1362,15 @@ pub struct EguiOutput {
     EguiInput,
     EguiContextPointerPosition,
     EguiContextPointerTouchId,
+    EguiContextMousePosition,
     EguiContextImeState,
+    EguiContextKeyRepeatState,
     EguiFullOutput,
     EguiRenderOutput,
     EguiOutput,
+    EguiWantsInput,
+    EguiContextScrollState,
+    EguiContextComputedScaleFactor,
     CursorIcon
 )]
 pub struct EguiContext {
@@ -666,6 +1406,17 @@ impl EguiContext {
     pub fn get_mut(&mut self) -> &mut egui::Context {
         &mut self.ctx
     }
+
+    /// Clones the underlying Egui context handle.
+    ///
+    /// Cloning is cheap, as [`egui::Context`] is reference-counted, and doesn't require locking
+    /// the context, so it doesn't need the `immutable_ctx` feature. The clone can be passed to
+    /// other threads or async tasks. Note that accessing the context through the clone still locks
+    /// Egui's internal `RwLock`, which may block UI systems accessing the same context.
+    #[must_use]
+    pub fn clone_context(&self) -> egui::Context {
+        self.ctx.clone()
+    }
 }
 
 // This query is actually unused, but we use it just to cheat a relevant error message.
@@ -676,6 +1427,7 @@ type EguiContextsQuery<'w, 's> = Query<
     'w,
     's,
     (
+        Entity,
         &'static mut EguiContext,
         Option<&'static PrimaryEguiContext>,
     ),
@@ -688,6 +1440,10 @@ pub struct EguiContexts<'w, 's> {
     q: EguiContextsQuery<'w, 's>,
     #[cfg(feature = "render")]
     user_textures: ResMut<'w, EguiUserTextures>,
+    #[cfg(feature = "render")]
+    asset_server: Option<Res<'w, AssetServer>>,
+    #[cfg(feature = "svg")]
+    svg_cache: ResMut<'w, svg::EguiSvgCache>,
 }
 
 #[allow(clippy::manual_try_fold)]
@@ -699,7 +1455,7 @@ impl EguiContexts<'_, '_> {
             Err(QuerySingleError::NoEntities(
                 core::any::type_name::<EguiContextsPrimaryQuery>().into(),
             )),
-            |result, (ctx, primary)| match (&result, primary) {
+            |result, (_entity, ctx, primary)| match (&result, primary) {
                 (Err(QuerySingleError::MultipleEntities(_)), _) => result,
                 (Err(QuerySingleError::NoEntities(_)), Some(_)) => Ok(ctx.into_inner().get_mut()),
                 (Err(QuerySingleError::NoEntities(_)), None) => result,
@@ -711,6 +1467,25 @@ impl EguiContexts<'_, '_> {
         )
     }
 
+    /// Returns the entity with the [`PrimaryEguiContext`] component.
+    #[inline]
+    pub fn primary_entity(&self) -> Result<Entity, QuerySingleError> {
+        self.q.iter().fold(
+            Err(QuerySingleError::NoEntities(
+                core::any::type_name::<EguiContextsPrimaryQuery>().into(),
+            )),
+            |result, (entity, _ctx, primary)| match (&result, primary) {
+                (Err(QuerySingleError::MultipleEntities(_)), _) => result,
+                (Err(QuerySingleError::NoEntities(_)), Some(_)) => Ok(entity),
+                (Err(QuerySingleError::NoEntities(_)), None) => result,
+                (Ok(_), Some(_)) => Err(QuerySingleError::MultipleEntities(
+                    core::any::type_name::<EguiContextsPrimaryQuery>().into(),
+                )),
+                (Ok(_), None) => result,
+            },
+        )
+    }
+
     /// Egui context of a specific entity.
     #[inline]
     pub fn ctx_for_entity_mut(
@@ -719,7 +1494,42 @@ impl EguiContexts<'_, '_> {
     ) -> Result<&mut egui::Context, QueryEntityError> {
         self.q
             .get_mut(entity)
-            .map(|(context, _primary)| context.into_inner().get_mut())
+            .map(|(_entity, context, _primary)| context.into_inner().get_mut())
+    }
+
+    /// Runs a closure with the Egui context with the [`PrimaryEguiContext`] component,
+    /// which is a shorthand for calling [`EguiContexts::ctx_mut`] and using the context.
+    ///
+    /// Like other UI code, it has to be called from a system running during the context pass
+    /// (e.g. in [`EguiPrimaryContextPass`]).
+    ///
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_egui::{egui, EguiContexts};
+    /// fn ui_system(mut contexts: EguiContexts) -> Result {
+    ///     contexts.run_ui(|ctx| {
+    ///         egui::Window::new("Hello").show(ctx, |ui| ui.label("world"));
+    ///     })?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[inline]
+    pub fn run_ui<R>(
+        &mut self,
+        add_contents: impl FnOnce(&mut egui::Context) -> R,
+    ) -> Result<R, QuerySingleError> {
+        Ok(add_contents(self.ctx_mut()?))
+    }
+
+    /// Runs a closure with the Egui context of a specific entity,
+    /// which is a shorthand for calling [`EguiContexts::ctx_for_entity_mut`] and using the context.
+    #[inline]
+    pub fn run_ui_for_entity<R>(
+        &mut self,
+        entity: Entity,
+        add_contents: impl FnOnce(&mut egui::Context) -> R,
+    ) -> Result<R, QueryEntityError> {
+        Ok(add_contents(self.ctx_for_entity_mut(entity)?))
     }
 
     /// Allows to get multiple contexts at the same time. This function is useful when you want
@@ -731,44 +1541,229 @@ impl EguiContexts<'_, '_> {
     ) -> Result<[&mut egui::Context; N], QueryEntityError> {
         self.q
             .get_many_mut(ids)
-            .map(|arr| arr.map(|(ctx, _primary_window)| ctx.into_inner().get_mut()))
+            .map(|arr| arr.map(|(_entity, ctx, _primary_window)| ctx.into_inner().get_mut()))
     }
 
-    /// Returns an Egui context with the [`PrimaryEguiContext`] component.
-    ///
-    /// Even though the mutable borrow isn't necessary, as the context is wrapped into `RwLock`,
-    /// using the immutable getter is gated with the `immutable_ctx` feature. Using the immutable
-    /// borrow is discouraged as it may cause unpredictable blocking in UI systems.
+    /// Allows to get a runtime-determined number of contexts at the same time, in the order of `ids`.
+    /// Unlike [`EguiContexts::ctx_for_entities_mut`], the number of contexts doesn't have to be known at compile time.
     ///
-    /// When the context is queried with `&mut EguiContext`, the Bevy scheduler is able to make
-    /// sure that the context isn't accessed concurrently and can perform other useful work
-    /// instead of busy-waiting.
-    #[cfg(feature = "immutable_ctx")]
-    #[inline]
-    pub fn ctx(&self) -> Result<&egui::Context, QuerySingleError> {
-        self.q.iter().fold(
-            Err(QuerySingleError::NoEntities(core::any::type_name::<
-                EguiContextsPrimaryQuery,
-            >())),
-            |result, (ctx, primary)| match (&result, primary) {
-                (Err(QuerySingleError::MultipleEntities(_)), _) => result,
-                (Err(QuerySingleError::NoEntities(_)), Some(_)) => Ok(ctx.get()),
-                (Err(QuerySingleError::NoEntities(_)), None) => result,
-                (Ok(_), Some(_)) => {
-                    Err(QuerySingleError::MultipleEntities(core::any::type_name::<
-                        EguiContextsPrimaryQuery,
-                    >()))
-                }
-                (Ok(_), None) => result,
-            },
-        )
+    /// Returns [`QueryEntityError::AliasedMutability`] if `ids` contain duplicates.
+    pub fn ctx_for_entities_vec_mut(
+        &mut self,
+        ids: &[Entity],
+    ) -> Result<Vec<&mut egui::Context>, QueryEntityError> {
+        for (i, &id) in ids.iter().enumerate() {
+            if ids[..i].contains(&id) {
+                return Err(QueryEntityError::AliasedMutability(id));
+            }
+            self.q.get(id)?;
+        }
+        let ids = bevy_ecs::entity::index_set::EntityIndexSet::from_iter(ids.iter().copied());
+        Ok(self
+            .q
+            .iter_many_unique_mut(ids)
+            .map(|(_entity, ctx, _primary_window)| ctx.into_inner().get_mut())
+            .collect())
     }
 
-    /// Egui context of a specific entity.
-    ///
-    /// Even though the mutable borrow isn't necessary, as the context is wrapped into `RwLock`,
-    /// using the immutable getter is gated with the `immutable_ctx` feature. Using the immutable
-    /// borrow is discouraged as it may cause unpredictable blocking in UI systems.
+    /// Returns the context that has keyboard focus, i.e. the one that receives keyboard input
+    /// and has a focused widget (see [`egui::Memory::focused`]).
+    ///
+    /// Keyboard input is received by [`FocusedNonWindowEguiContext`] if it exists (pass the resource
+    /// as `focused_non_window_context`), or by the contexts of the focused window otherwise.
+    /// If several contexts of the focused window (e.g. in split screen) have focused widgets,
+    /// any of them can be returned.
+    pub fn focused_context(
+        &mut self,
+        focused_non_window_context: Option<&FocusedNonWindowEguiContext>,
+    ) -> Option<Entity> {
+        let focused_non_window_context = focused_non_window_context.map(|context| context.0);
+        self.q
+            .iter_mut()
+            .find_map(|(entity, mut context, _primary)| {
+                let ctx = context.get_mut();
+                let receives_keyboard_input = match focused_non_window_context {
+                    Some(focused_non_window_context) => entity == focused_non_window_context,
+                    // The viewport focus reflects the focus of the window the context renders to.
+                    None => ctx.input(|input| input.viewport().focused == Some(true)),
+                };
+                (receives_keyboard_input && ctx.memory(|memory| memory.focused().is_some()))
+                    .then_some(entity)
+            })
+    }
+
+    /// Returns the rectangle (in Egui points) of a widget with the given id in the Egui context of a specific entity.
+    ///
+    /// The rectangle is read from the widget info of the current or the previous pass (see [`egui::Context::read_response`]).
+    /// Returns `Ok(None)` if the widget wasn't shown. Can be used for drawing Bevy-side overlays, such as
+    /// tutorial highlights, pointing at specific UI elements.
+    ///
+    /// Egui points match Bevy logical pixels only if [`EguiContextSettings::scale_factor`] and
+    /// [`EguiGlobalSettings::global_scale_factor`] equal `1.0`. Multiplying the rectangle by
+    /// [`EguiContextComputedScaleFactor::scale_factor`] converts it into physical pixels:
+    ///
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_egui::{egui, EguiContextComputedScaleFactor, EguiContexts};
+    /// fn overlay_system(
+    ///     mut contexts: EguiContexts,
+    ///     cameras: Query<(Entity, &Camera, &EguiContextComputedScaleFactor)>,
+    /// ) -> Result {
+    ///     for (entity, camera, computed_scale_factor) in &cameras {
+    ///         let Some(rect) = contexts.widget_rect(entity, egui::Id::new("play_button"))? else {
+    ///             continue;
+    ///         };
+    ///         // Egui points -> physical pixels -> Bevy logical pixels.
+    ///         let scale = computed_scale_factor.scale_factor / camera.target_scaling_factor().unwrap_or(1.0);
+    ///         let rect = Rect::from_corners(
+    ///             Vec2::new(rect.min.x, rect.min.y) * scale,
+    ///             Vec2::new(rect.max.x, rect.max.y) * scale,
+    ///         );
+    ///         // Draw an overlay over `rect`...
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn widget_rect(
+        &mut self,
+        entity: Entity,
+        id: egui::Id,
+    ) -> Result<Option<egui::Rect>, QueryEntityError> {
+        let ctx = self.ctx_for_entity_mut(entity)?;
+        Ok(ctx.read_response(id).map(|response| response.rect))
+    }
+
+    /// Returns the size (in Egui points) that `text` would take when laid out by the Egui context of a specific entity.
+    ///
+    /// Can be called outside UI systems (e.g. for reserving space for Egui text in Bevy UI), as it uses the fonts
+    /// of the last pass. Returns `Ok(None)` if the context hasn't run a pass yet, as Egui doesn't load fonts until then.
+    /// Pass [`f32::INFINITY`] as `wrap_width` (also in Egui points) to disable wrapping.
+    /// See [`EguiContexts::widget_rect`] for converting Egui points into Bevy logical pixels.
+    pub fn layout_text(
+        &mut self,
+        entity: Entity,
+        text: impl Into<String>,
+        font_id: egui::FontId,
+        wrap_width: f32,
+    ) -> Result<Option<egui::Vec2>, QueryEntityError> {
+        let ctx = self.ctx_for_entity_mut(entity)?;
+        if ctx.cumulative_pass_nr() == 0 {
+            return Ok(None);
+        }
+        let text = text.into();
+        Ok(Some(ctx.fonts(|fonts| {
+            fonts
+                .layout(text, font_id, egui::Color32::PLACEHOLDER, wrap_width)
+                .size()
+        })))
+    }
+
+    /// Sets the [`egui::Style`] of the Egui context with the [`PrimaryEguiContext`] component.
+    #[inline]
+    pub fn set_style(
+        &mut self,
+        style: impl Into<std::sync::Arc<egui::Style>>,
+    ) -> Result<(), QuerySingleError> {
+        self.ctx_mut()?.set_style(style);
+        Ok(())
+    }
+
+    /// Sets the [`egui::Style`] of the Egui context of a specific entity.
+    #[inline]
+    pub fn set_style_for_entity(
+        &mut self,
+        entity: Entity,
+        style: impl Into<std::sync::Arc<egui::Style>>,
+    ) -> Result<(), QueryEntityError> {
+        self.ctx_for_entity_mut(entity)?.set_style(style);
+        Ok(())
+    }
+
+    /// Modifies the [`egui::Style`] of the Egui context with the [`PrimaryEguiContext`] component in place.
+    ///
+    /// Unlike [`EguiContexts::set_style`], this doesn't require building the whole style to change a single field.
+    ///
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_egui::{egui, EguiContexts};
+    /// fn setup_style_system(mut contexts: EguiContexts) -> Result {
+    ///     contexts.modify_style(|style| style.spacing.item_spacing = egui::vec2(10.0, 6.0))?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[inline]
+    pub fn modify_style(
+        &mut self,
+        modify: impl FnOnce(&mut egui::Style),
+    ) -> Result<(), QuerySingleError> {
+        self.ctx_mut()?.style_mut(modify);
+        Ok(())
+    }
+
+    /// Modifies the [`egui::Style`] of the Egui context of a specific entity in place.
+    #[inline]
+    pub fn modify_style_for_entity(
+        &mut self,
+        entity: Entity,
+        modify: impl FnOnce(&mut egui::Style),
+    ) -> Result<(), QueryEntityError> {
+        self.ctx_for_entity_mut(entity)?.style_mut(modify);
+        Ok(())
+    }
+
+    /// Sets the [`egui::Visuals`] of the Egui context with the [`PrimaryEguiContext`] component.
+    #[inline]
+    pub fn set_visuals(&mut self, visuals: egui::Visuals) -> Result<(), QuerySingleError> {
+        self.ctx_mut()?.set_visuals(visuals);
+        Ok(())
+    }
+
+    /// Sets the [`egui::Visuals`] of the Egui context of a specific entity.
+    #[inline]
+    pub fn set_visuals_for_entity(
+        &mut self,
+        entity: Entity,
+        visuals: egui::Visuals,
+    ) -> Result<(), QueryEntityError> {
+        self.ctx_for_entity_mut(entity)?.set_visuals(visuals);
+        Ok(())
+    }
+
+    /// Returns an Egui context with the [`PrimaryEguiContext`] component.
+    ///
+    /// Even though the mutable borrow isn't necessary, as the context is wrapped into `RwLock`,
+    /// using the immutable getter is gated with the `immutable_ctx` feature. Using the immutable
+    /// borrow is discouraged as it may cause unpredictable blocking in UI systems.
+    ///
+    /// When the context is queried with `&mut EguiContext`, the Bevy scheduler is able to make
+    /// sure that the context isn't accessed concurrently and can perform other useful work
+    /// instead of busy-waiting.
+    #[cfg(feature = "immutable_ctx")]
+    #[inline]
+    pub fn ctx(&self) -> Result<&egui::Context, QuerySingleError> {
+        self.q.iter().fold(
+            Err(QuerySingleError::NoEntities(core::any::type_name::<
+                EguiContextsPrimaryQuery,
+            >())),
+            |result, (_entity, ctx, primary)| match (&result, primary) {
+                (Err(QuerySingleError::MultipleEntities(_)), _) => result,
+                (Err(QuerySingleError::NoEntities(_)), Some(_)) => Ok(ctx.get()),
+                (Err(QuerySingleError::NoEntities(_)), None) => result,
+                (Ok(_), Some(_)) => {
+                    Err(QuerySingleError::MultipleEntities(core::any::type_name::<
+                        EguiContextsPrimaryQuery,
+                    >()))
+                }
+                (Ok(_), None) => result,
+            },
+        )
+    }
+
+    /// Egui context of a specific entity.
+    ///
+    /// Even though the mutable borrow isn't necessary, as the context is wrapped into `RwLock`,
+    /// using the immutable getter is gated with the `immutable_ctx` feature. Using the immutable
+    /// borrow is discouraged as it may cause unpredictable blocking in UI systems.
     ///
     /// When the context is queried with `&mut EguiContext`, the Bevy scheduler is able to make
     /// sure that the context isn't accessed concurrently and can perform other useful work
@@ -776,7 +1771,28 @@ impl EguiContexts<'_, '_> {
     #[inline]
     #[cfg(feature = "immutable_ctx")]
     pub fn ctx_for_entity(&self, entity: Entity) -> Result<&egui::Context, QueryEntityError> {
-        self.q.get(entity).map(|(context, _primary)| context.get())
+        self.q
+            .get(entity)
+            .map(|(_entity, context, _primary)| context.get())
+    }
+
+    /// Returns [`egui::Context::wants_keyboard_input`] of the Egui context with the [`PrimaryEguiContext`] component.
+    ///
+    /// Is useful for checking whether game hotkeys should be processed in the current frame.
+    /// Unlike the [`egui_wants_any_keyboard_input`] run condition, only the primary context is checked.
+    #[cfg(feature = "immutable_ctx")]
+    #[inline]
+    pub fn primary_wants_keyboard_input(&self) -> Result<bool, QuerySingleError> {
+        self.ctx().map(egui::Context::wants_keyboard_input)
+    }
+
+    /// Returns [`egui::Context::wants_pointer_input`] of the Egui context with the [`PrimaryEguiContext`] component.
+    ///
+    /// Unlike the [`egui_wants_any_pointer_input`] run condition, only the primary context is checked.
+    #[cfg(feature = "immutable_ctx")]
+    #[inline]
+    pub fn primary_wants_pointer_input(&self) -> Result<bool, QuerySingleError> {
+        self.ctx().map(egui::Context::wants_pointer_input)
     }
 
     /// Can accept either a strong or a weak handle.
@@ -792,6 +1808,28 @@ impl EguiContexts<'_, '_> {
         self.user_textures.add_image(image)
     }
 
+    /// Works the same way as [`EguiContexts::add_image`], but also sets the alpha mode of the texture,
+    /// see [`EguiUserTextureAlpha`].
+    ///
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_egui::{EguiContexts, EguiUserTextureAlpha};
+    /// fn ui_system(mut contexts: EguiContexts, asset_server: Res<AssetServer>) {
+    ///     let icon = contexts.add_image_with_options(
+    ///         asset_server.load("icon.png"),
+    ///         EguiUserTextureAlpha::PremultiplyOnUpload,
+    ///     );
+    /// }
+    /// ```
+    #[cfg(feature = "render")]
+    pub fn add_image_with_options(
+        &mut self,
+        image: Handle<Image>,
+        alpha: EguiUserTextureAlpha,
+    ) -> egui::TextureId {
+        self.user_textures.add_image_with_options(image, alpha)
+    }
+
     /// Removes the image handle and an Egui texture id associated with it.
     #[cfg(feature = "render")]
     #[track_caller]
@@ -806,6 +1844,70 @@ impl EguiContexts<'_, '_> {
     pub fn image_id(&self, image: &Handle<Image>) -> Option<egui::TextureId> {
         self.user_textures.image_id(image)
     }
+
+    /// Rasterizes SVG data into an image of the given size (in pixels) and adds it as a user texture.
+    ///
+    /// The rasterized images are cached: adding the same SVG data with the same size again returns
+    /// the same texture id without rasterizing it. The texture is kept until [`EguiContexts::remove_svg`] is called.
+    ///
+    /// ```rust
+    /// # use bevy::prelude::*;
+    /// # use bevy_egui::{egui, EguiContexts};
+    /// const ICON: &[u8] = br#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 16 16"><circle cx="8" cy="8" r="6"/></svg>"#;
+    ///
+    /// fn ui_system(mut contexts: EguiContexts) -> Result {
+    ///     let icon = contexts.add_svg(ICON, UVec2::splat(32))?;
+    ///     egui::Window::new("Icon").show(contexts.ctx_mut()?, |ui| {
+    ///         ui.image(egui::load::SizedTexture::new(icon, [16.0, 16.0]));
+    ///     });
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "svg")]
+    pub fn add_svg(
+        &mut self,
+        bytes: &[u8],
+        size: bevy_math::UVec2,
+    ) -> Result<egui::TextureId, svg::EguiSvgError> {
+        if let Some(image) = self.svg_cache.get(bytes, size) {
+            return Ok(self.user_textures.add_image(image));
+        }
+
+        let asset_server = self
+            .asset_server
+            .as_deref()
+            .ok_or(svg::EguiSvgError::MissingAssetServer)?;
+        let image = asset_server.add(svg::rasterize_svg(bytes, size)?);
+        self.svg_cache.insert(bytes, size, image.clone());
+        Ok(self.user_textures.add_image(image))
+    }
+
+    /// Removes a texture added with [`EguiContexts::add_svg`] with the same SVG data and size.
+    #[cfg(feature = "svg")]
+    pub fn remove_svg(&mut self, bytes: &[u8], size: bevy_math::UVec2) -> Option<egui::TextureId> {
+        let image = self.svg_cache.remove(bytes, size)?;
+        self.user_textures.remove_image(&image)
+    }
+
+    /// Returns an associated Egui texture id, but only if the image has finished loading.
+    ///
+    /// Returns [`None`] while the image is being loaded by the [`AssetServer`] (or if loading has failed),
+    /// which lets you show a placeholder instead of a blank texture. Images that weren't loaded
+    /// via the [`AssetServer`] (e.g. added to [`Assets<Image>`] directly) are considered loaded.
+    #[cfg(feature = "render")]
+    #[must_use]
+    #[track_caller]
+    pub fn image_id_when_loaded(&self, image: &Handle<Image>) -> Option<egui::TextureId> {
+        let is_loaded = self
+            .asset_server
+            .as_deref()
+            .and_then(|asset_server| asset_server.get_load_state(image.id()))
+            .map_or(true, |load_state| load_state.is_loaded());
+        if !is_loaded {
+            return None;
+        }
+        self.user_textures.image_id(image)
+    }
 }
 
 /// A resource for storing `bevy_egui` user textures.
@@ -813,7 +1915,45 @@ impl EguiContexts<'_, '_> {
 #[cfg(feature = "render")]
 pub struct EguiUserTextures {
     textures: HashMap<Handle<Image>, u64>,
+    alpha_modes: HashMap<u64, EguiUserTextureAlpha>,
+    premultiplied_copies: HashMap<bevy_asset::AssetId<Image>, Handle<Image>>,
+    // Images that can't be premultiplied, until they are modified.
+    rejected_premultiplied_images: HashSet<bevy_asset::AssetId<Image>>,
     free_list: Vec<u64>,
+    reserved_ranges: Vec<ReservedUserTextureRange>,
+}
+
+/// Describes how the alpha of a user texture is blended, see [`EguiUserTextures::add_image_with_options`].
+///
+/// Bevy images are usually stored with straight (not premultiplied) alpha, which Egui premultiplies
+/// after sampling. With linear filtering, this can produce dark edges around semi-transparent pixels
+/// (e.g. soft edges of icons), as colors of transparent pixels get blended in as well.
+#[cfg(feature = "render")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum EguiUserTextureAlpha {
+    /// The image has straight alpha, it's premultiplied after sampling.
+    #[default]
+    Straight,
+    /// The image data is already premultiplied (e.g. images rendered by cameras), it's sampled as is.
+    Premultiplied,
+    /// `bevy_egui` creates a premultiplied copy of the image when it's loaded or modified, which is then sampled as is.
+    ///
+    /// The copy is kept until the image is removed from [`EguiUserTextures`] and doubles the memory used by the image.
+    /// Supported only for the `Rgba8Unorm`, `Rgba8UnormSrgb`, `Bgra8Unorm` and `Bgra8UnormSrgb` texture formats,
+    /// other images are treated as [`EguiUserTextureAlpha::Straight`]. The image data must be available
+    /// in the main world (see [`bevy_asset::RenderAssetUsages::MAIN_WORLD`]).
+    PremultiplyOnUpload,
+}
+
+/// A range of user texture ids reserved with [`EguiUserTextures::reserve_range`].
+#[derive(Clone)]
+#[cfg(feature = "render")]
+struct ReservedUserTextureRange {
+    range: std::ops::Range<u64>,
+    // Ids of removed images.
+    free_list: Vec<u64>,
+    // The lowest id of the range that has never been handed out.
+    next_fresh_id: u64,
 }
 
 #[cfg(feature = "render")]
@@ -821,7 +1961,11 @@ impl Default for EguiUserTextures {
     fn default() -> Self {
         Self {
             textures: HashMap::default(),
+            alpha_modes: HashMap::default(),
+            premultiplied_copies: HashMap::default(),
+            rejected_premultiplied_images: HashSet::default(),
             free_list: vec![0],
+            reserved_ranges: Vec::new(),
         }
     }
 }
@@ -836,31 +1980,162 @@ impl EguiUserTextures {
     ///
     /// You'll want to pass a strong handle if a texture is used only in Egui and there are no
     /// handle copies stored anywhere else.
+    ///
+    /// Ids reserved with [`EguiUserTextures::reserve_range`] are never returned by this function.
     pub fn add_image(&mut self, image: Handle<Image>) -> egui::TextureId {
-        let id = *self.textures.entry(image.clone()).or_insert_with(|| {
-            let id = self
-                .free_list
-                .pop()
-                .expect("free list must contain at least 1 element");
-            log::debug!("Add a new image (id: {}, handle: {:?})", id, image);
-            if self.free_list.is_empty() {
-                self.free_list.push(id.checked_add(1).expect("out of ids"));
-            }
-            id
-        });
+        if let Some(&id) = self.textures.get(&image) {
+            return egui::TextureId::User(id);
+        }
+
+        let id = self
+            .free_list
+            .pop()
+            .expect("free list must contain at least 1 element");
+        log::debug!("Add a new image (id: {}, handle: {:?})", id, image);
+        if self.free_list.is_empty() {
+            let next_id = self.next_unreserved_id(id.checked_add(1).expect("out of ids"));
+            self.free_list.push(next_id);
+        }
+        self.textures.insert(image, id);
         egui::TextureId::User(id)
     }
 
+    /// Reserves `len` ids starting from `start`, so that [`EguiUserTextures::add_image`] doesn't hand them out.
+    ///
+    /// Use [`EguiUserTextures::add_image_in_range`] to add images with ids from the reserved range,
+    /// e.g. if a plugin assigns meaning to specific user texture ids (like sending them over the network).
+    ///
+    /// Returns `false` (reserving nothing) if the range is empty, overlaps another reserved range,
+    /// or contains ids of already added images.
+    #[must_use]
+    pub fn reserve_range(&mut self, start: u64, len: u64) -> bool {
+        let Some(end) = start.checked_add(len) else {
+            return false;
+        };
+        let range = start..end;
+        if range.is_empty()
+            || self
+                .reserved_ranges
+                .iter()
+                .any(|reserved| reserved.range.start < end && start < reserved.range.end)
+            || self.textures.values().any(|id| range.contains(id))
+        {
+            return false;
+        }
+
+        let next_fresh_id = self.free_list[0];
+        self.free_list.retain(|id| !range.contains(id));
+        self.reserved_ranges.push(ReservedUserTextureRange {
+            range: range.clone(),
+            free_list: Vec::new(),
+            next_fresh_id: start,
+        });
+        if range.contains(&next_fresh_id) {
+            let next_id = self.next_unreserved_id(next_fresh_id);
+            self.free_list.insert(0, next_id);
+        }
+        true
+    }
+
+    /// Same as [`EguiUserTextures::add_image`], but takes an id from a range reserved with
+    /// [`EguiUserTextures::reserve_range`] (identified by its `start`).
+    ///
+    /// Returns `None` if there's no reserved range starting at `start` or all of its ids are taken.
+    /// If the image has already been added, returns its current id, regardless of the range.
+    pub fn add_image_in_range(
+        &mut self,
+        image: Handle<Image>,
+        start: u64,
+    ) -> Option<egui::TextureId> {
+        if let Some(&id) = self.textures.get(&image) {
+            return Some(egui::TextureId::User(id));
+        }
+
+        let reserved = self
+            .reserved_ranges
+            .iter_mut()
+            .find(|reserved| reserved.range.start == start)?;
+        let id = match reserved.free_list.pop() {
+            Some(id) => id,
+            None if reserved.next_fresh_id < reserved.range.end => {
+                reserved.next_fresh_id += 1;
+                reserved.next_fresh_id - 1
+            }
+            None => return None,
+        };
+        log::debug!("Add a new image (id: {}, handle: {:?})", id, image);
+        self.textures.insert(image, id);
+        Some(egui::TextureId::User(id))
+    }
+
+    /// Works the same way as [`EguiUserTextures::add_image`], but also sets the alpha mode of the texture.
+    ///
+    /// If the image is already added, only the alpha mode is updated.
+    pub fn add_image_with_options(
+        &mut self,
+        image: Handle<Image>,
+        alpha: EguiUserTextureAlpha,
+    ) -> egui::TextureId {
+        let image_id = image.id();
+        let texture_id = self.add_image(image);
+        let egui::TextureId::User(id) = texture_id else {
+            unreachable!("user textures always have user ids");
+        };
+        if alpha != EguiUserTextureAlpha::PremultiplyOnUpload {
+            self.premultiplied_copies.remove(&image_id);
+            self.rejected_premultiplied_images.remove(&image_id);
+        }
+        match alpha {
+            EguiUserTextureAlpha::Straight => self.alpha_modes.remove(&id),
+            _ => self.alpha_modes.insert(id, alpha),
+        };
+        texture_id
+    }
+
+    /// Returns the alpha mode of a user texture.
+    #[must_use]
+    pub fn alpha_mode(&self, id: u64) -> EguiUserTextureAlpha {
+        self.alpha_modes.get(&id).copied().unwrap_or_default()
+    }
+
+    /// Returns the premultiplied copy of an image added with [`EguiUserTextureAlpha::PremultiplyOnUpload`],
+    /// if it's already created.
+    #[must_use]
+    pub fn premultiplied_copy(&self, image: bevy_asset::AssetId<Image>) -> Option<&Handle<Image>> {
+        self.premultiplied_copies.get(&image)
+    }
+
     /// Removes the image handle and an Egui texture id associated with it.
     pub fn remove_image(&mut self, image: &Handle<Image>) -> Option<egui::TextureId> {
         let id = self.textures.remove(image);
         log::debug!("Remove image (id: {:?}, handle: {:?})", id, image);
+        self.premultiplied_copies.remove(&image.id());
+        self.rejected_premultiplied_images.remove(&image.id());
         if let Some(id) = id {
-            self.free_list.push(id);
+            self.alpha_modes.remove(&id);
+            match self
+                .reserved_ranges
+                .iter_mut()
+                .find(|reserved| reserved.range.contains(&id))
+            {
+                Some(reserved) => reserved.free_list.push(id),
+                None => self.free_list.push(id),
+            }
         }
         id.map(egui::TextureId::User)
     }
 
+    fn next_unreserved_id(&self, mut id: u64) -> u64 {
+        while let Some(reserved) = self
+            .reserved_ranges
+            .iter()
+            .find(|reserved| reserved.range.contains(&id))
+        {
+            id = reserved.range.end;
+        }
+        id
+    }
+
     /// Returns an associated Egui texture id.
     #[must_use]
     pub fn image_id(&self, image: &Handle<Image>) -> Option<egui::TextureId> {
@@ -870,6 +2145,248 @@ impl EguiUserTextures {
     }
 }
 
+/// Stores the effective scale factor of an Egui context (i.e. its [`egui::Context::pixels_per_point`]).
+///
+/// If [`EguiContextSettings::fixed_logical_size`] is set, it's the upscale factor of the output instead,
+/// while the pixels per point value of the context equals `1.0`.
+///
+/// The value is updated in [`EguiPreUpdateSet::InitContexts`] and can be read by systems that need
+/// to convert between Egui points and physical pixels.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct EguiContextComputedScaleFactor {
+    /// Scale factor ([`EguiContextSettings::scale_factor`] multiplied by [`EguiGlobalSettings::global_scale_factor`]
+    #[cfg_attr(
+        feature = "render",
+        doc = "and [`bevy_render::camera::Camera::target_scaling_factor`] or [`EguiOffscreenContext::scale_factor`])."
+    )]
+    #[cfg_attr(
+        not(feature = "render"),
+        doc = "and the camera target scaling factor or [`EguiOffscreenContext::scale_factor`])."
+    )]
+    pub scale_factor: f32,
+}
+
+impl Default for EguiContextComputedScaleFactor {
+    fn default() -> Self {
+        Self { scale_factor: 1.0 }
+    }
+}
+
+/// Mirrors commonly used [`egui::Options`], insert it into an Egui context entity to configure the options declaratively.
+///
+/// The options are applied to the context whenever the component changes (see [`apply_egui_context_options_system`]).
+/// Options that aren't mirrored keep their current values (Egui defaults, unless changed with [`egui::Context::options_mut`]).
+///
+/// Tooltip settings are part of [`egui::style::Interaction`], they are applied to the styles of all themes.
+///
+/// Egui doesn't have a context-wide option controlling whether the text of a [`egui::TextEdit`] gets selected
+/// when it gains focus, so it can't be mirrored here. The selection can be reset per widget instead,
+/// by overriding the cursor range of its [`egui::text_edit::TextEditState`]:
+///
+/// ```rust
+/// # use bevy_egui::egui;
+/// fn text_field(ui: &mut egui::Ui, text: &mut String) -> egui::Response {
+///     let mut output = egui::TextEdit::singleline(text).show(ui);
+///     if output.response.gained_focus() {
+///         // Place the cursor at the end of the text instead of keeping the text selected.
+///         let cursor = egui::text::CCursor::new(text.chars().count());
+///         output
+///             .state
+///             .cursor
+///             .set_char_range(Some(egui::text::CCursorRange::one(cursor)));
+///         output.state.store(ui.ctx(), output.response.id);
+///     }
+///     output.response
+/// }
+/// ```
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_egui::{EguiContextOptions, PrimaryEguiContext};
+/// fn setup_system(mut commands: Commands, primary_context: Single<Entity, With<PrimaryEguiContext>>) {
+///     let mut options = EguiContextOptions::default();
+///     options.input_options.line_scroll_speed = 80.0;
+///     options.tooltip_delay = 0.8;
+///     commands.entity(*primary_context).insert(options);
+/// }
+/// ```
+#[derive(Component, Clone, Debug)]
+pub struct EguiContextOptions {
+    /// Mirrors [`egui::Options::zoom_with_keyboard`].
+    pub zoom_with_keyboard: bool,
+    /// Mirrors [`egui::Options::reduce_texture_memory`].
+    pub reduce_texture_memory: bool,
+    /// Mirrors [`egui::Options::max_passes`], the maximum number of passes per frame in the multi-pass mode.
+    ///
+    /// Raising it can help with layouts that repeatedly call [`egui::Context::request_discard`]
+    /// (e.g. complex grids), which otherwise may be shown with a wrong layout for a frame.
+    /// Note that each pass runs the whole [`EguiMultipassSchedule`] again, so a higher limit can multiply
+    /// the UI cost of such frames.
+    pub max_passes: std::num::NonZeroUsize,
+    /// Mirrors [`egui::Options::warn_on_id_clash`].
+    pub warn_on_id_clash: bool,
+    /// Mirrors [`egui::Options::input_options`] (scroll speeds, click distance and delays, etc.).
+    pub input_options: egui::InputOptions,
+    /// Mirrors [`egui::style::Interaction::tooltip_delay`], the delay in seconds before a tooltip is shown.
+    pub tooltip_delay: f32,
+    /// Mirrors [`egui::style::Interaction::tooltip_grace_time`], the time in seconds after closing a tooltip
+    /// during which another tooltip is shown immediately.
+    pub tooltip_grace_time: f32,
+    /// Mirrors [`egui::style::Interaction::show_tooltips_only_when_still`].
+    pub show_tooltips_only_when_still: bool,
+}
+
+impl Default for EguiContextOptions {
+    fn default() -> Self {
+        let options = egui::Options::default();
+        let interaction = egui::style::Interaction::default();
+        Self {
+            zoom_with_keyboard: options.zoom_with_keyboard,
+            reduce_texture_memory: options.reduce_texture_memory,
+            max_passes: options.max_passes,
+            warn_on_id_clash: options.warn_on_id_clash,
+            input_options: options.input_options,
+            tooltip_delay: interaction.tooltip_delay,
+            tooltip_grace_time: interaction.tooltip_grace_time,
+            show_tooltips_only_when_still: interaction.show_tooltips_only_when_still,
+        }
+    }
+}
+
+/// Applies [`EguiContextOptions`] to contexts when the component is inserted or changed.
+pub fn apply_egui_context_options_system(
+    mut contexts: Query<(&mut EguiContext, &EguiContextOptions), Changed<EguiContextOptions>>,
+) {
+    for (mut context, context_options) in contexts.iter_mut() {
+        let ctx = context.get_mut();
+        ctx.options_mut(|options| {
+            options.zoom_with_keyboard = context_options.zoom_with_keyboard;
+            options.reduce_texture_memory = context_options.reduce_texture_memory;
+            options.max_passes = context_options.max_passes;
+            options.warn_on_id_clash = context_options.warn_on_id_clash;
+            options.input_options = context_options.input_options.clone();
+        });
+        ctx.all_styles_mut(|style| {
+            style.interaction.tooltip_delay = context_options.tooltip_delay;
+            style.interaction.tooltip_grace_time = context_options.tooltip_grace_time;
+            style.interaction.show_tooltips_only_when_still =
+                context_options.show_tooltips_only_when_still;
+        });
+    }
+}
+
+/// Applies [`EguiContextSettings::embed_viewports`] to contexts whenever the settings change.
+pub fn update_embed_viewports_system(
+    mut contexts: Query<(&mut EguiContext, &EguiContextSettings), Changed<EguiContextSettings>>,
+) {
+    for (mut context, settings) in contexts.iter_mut() {
+        let ctx = context.get_mut();
+        if ctx.embed_viewports() != settings.embed_viewports {
+            ctx.set_embed_viewports(settings.embed_viewports);
+        }
+    }
+}
+
+/// Applies [`EguiContextSettings::allow_ctrl_scroll_zoom`] to [`egui::InputOptions::zoom_modifier`].
+///
+/// The modifier is touched only when the setting changes: disabling it clears the modifier and remembers
+/// the previous one, enabling it again restores the remembered modifier. This way, other settings changes
+/// don't overwrite a modifier set with [`egui::Context::options_mut`]. If [`EguiContextOptions`] change
+/// while zooming is disabled, their modifier is remembered instead and the context modifier stays cleared.
+pub fn update_scroll_zoom_modifier_system(
+    mut contexts: Query<
+        (
+            Entity,
+            &mut EguiContext,
+            &EguiContextSettings,
+            Option<Ref<EguiContextOptions>>,
+        ),
+        Or<(Changed<EguiContextSettings>, Changed<EguiContextOptions>)>,
+    >,
+    mut removed_contexts: RemovedComponents<EguiContext>,
+    mut disabled_zoom_modifiers: Local<HashMap<Entity, egui::Modifiers>>,
+) {
+    for entity in removed_contexts.read() {
+        disabled_zoom_modifiers.remove(&entity);
+    }
+
+    for (entity, mut context, settings, context_options) in contexts.iter_mut() {
+        let ctx = context.get_mut();
+        match (
+            settings.allow_ctrl_scroll_zoom,
+            disabled_zoom_modifiers.contains_key(&entity),
+        ) {
+            (true, true) => {
+                let zoom_modifier = disabled_zoom_modifiers
+                    .remove(&entity)
+                    .expect("Expected a remembered zoom modifier");
+                ctx.options_mut(|options| options.input_options.zoom_modifier = zoom_modifier);
+            }
+            (false, false) => {
+                let zoom_modifier = ctx.options(|options| options.input_options.zoom_modifier);
+                disabled_zoom_modifiers.insert(entity, zoom_modifier);
+                // An empty modifier set doesn't match any pressed modifiers.
+                ctx.options_mut(|options| {
+                    options.input_options.zoom_modifier = egui::Modifiers::NONE;
+                });
+            }
+            (false, true) => {
+                // `apply_egui_context_options_system` has just written the options' modifier.
+                if let Some(context_options) =
+                    context_options.filter(|context_options| context_options.is_changed())
+                {
+                    disabled_zoom_modifiers
+                        .insert(entity, context_options.input_options.zoom_modifier);
+                    ctx.options_mut(|options| {
+                        options.input_options.zoom_modifier = egui::Modifiers::NONE;
+                    });
+                }
+            }
+            (true, false) => {}
+        }
+    }
+}
+
+/// A marker component that is inserted into an Egui context entity once the context has run a pass
+/// with a known screen size (i.e. [`egui::RawInput::screen_rect`] was set).
+///
+/// Use the [`egui_context_ready`] run condition to skip layout-sensitive systems until the primary context is ready.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct EguiContextReady;
+
+/// A run condition that returns `true` if the [`PrimaryEguiContext`] has the [`EguiContextReady`] component.
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_egui::{egui_context_ready, EguiPrimaryContextPass};
+/// # fn layout_sensitive_ui_system() {}
+/// # let mut app = App::new();
+/// app.add_systems(
+///     EguiPrimaryContextPass,
+///     layout_sensitive_ui_system.run_if(egui_context_ready),
+/// );
+/// ```
+pub fn egui_context_ready(
+    primary_context: Query<(), (With<PrimaryEguiContext>, With<EguiContextReady>)>,
+) -> bool {
+    !primary_context.is_empty()
+}
+
+/// Inserts [`EguiContextReady`] into contexts that have run a pass with a known screen size.
+pub fn mark_egui_contexts_ready_system(
+    mut commands: Commands,
+    mut contexts: Query<(Entity, &mut EguiContext), Without<EguiContextReady>>,
+) {
+    for (entity, mut context) in contexts.iter_mut() {
+        if context
+            .get_mut()
+            .input(|input| input.raw.screen_rect.is_some())
+        {
+            commands.entity(entity).insert(EguiContextReady);
+        }
+    }
+}
+
 /// Stores physical size and scale factor, is used as a helper to calculate logical size.
 /// The component lives only in the Render world.
 #[derive(Component, Debug, Default, Clone, Copy, PartialEq)]
@@ -878,6 +2395,157 @@ pub struct RenderComputedScaleFactor {
     pub scale_factor: f32,
 }
 
+/// Stores [`EguiContextSettings`] that affect rendering.
+/// The component lives only in the Render world.
+#[cfg(feature = "render")]
+#[derive(Component, Debug, Default, Clone, PartialEq)]
+pub struct RenderEguiContextSettings {
+    /// Reflects the value of [`EguiContextSettings::color_space`].
+    pub color_space: EguiColorSpace,
+    /// Reflects the value of [`EguiContextSettings::render_background`].
+    pub render_background: Option<egui::Color32>,
+    /// Reflects the value of [`EguiContextSettings::flip_y`].
+    pub flip_y: bool,
+    /// Reflects the value of [`EguiContextTransform`] (identity if the component is absent).
+    pub transform: bevy_math::Mat3,
+}
+
+/// A 2D affine transform applied to the vertex positions of the Egui context output when rendering,
+/// can be used to rotate or shear a panel (e.g. to draw it on a tilted in-world screen).
+///
+/// The transform is applied in Egui points, with the origin in the top left corner of the context,
+/// before the positions are scaled to the render target.
+/// Clip rectangles are replaced with the bounding boxes of the transformed ones, as scissor rectangles
+/// can't be rotated. Pointer input isn't transformed, so interaction with the transformed UI
+/// requires feeding the inverse transformed positions to the context manually.
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_egui::{EguiContextTransform, PrimaryEguiContext};
+/// fn setup_system(mut commands: Commands, primary_context: Single<Entity, With<PrimaryEguiContext>>) {
+///     commands.entity(*primary_context).insert(EguiContextTransform(
+///         Mat3::from_translation(Vec2::new(200.0, 100.0)) * Mat3::from_angle(0.2),
+///     ));
+/// }
+/// ```
+#[cfg(feature = "render")]
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct EguiContextTransform(pub bevy_math::Mat3);
+
+#[cfg(feature = "render")]
+impl Default for EguiContextTransform {
+    fn default() -> Self {
+        Self(bevy_math::Mat3::IDENTITY)
+    }
+}
+
+/// Renders the UI of another Egui context on top of the camera this component is added to.
+///
+/// The UI systems run only once for the mirrored context, its output is just drawn to several cameras.
+/// The value is the entity of the camera with the mirrored [`EguiContext`]. A camera can't have both
+/// an Egui context and a mirror.
+///
+/// Shapes are tessellated for [`EguiContextComputedScaleFactor`] of the mirrored context,
+/// and the mirror renders them with the same `pixels_per_point`, without rescaling. Mirroring
+/// a context onto a camera with a different viewport size or target scale factor results in
+/// the UI being cropped or not filling the viewport. Input is processed only for the mirrored context.
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_egui::{EguiContextMirror, PrimaryEguiContext};
+/// fn setup_mirror_system(
+///     mut commands: Commands,
+///     primary_context: Single<Entity, With<PrimaryEguiContext>>,
+/// ) {
+///     commands.spawn((
+///         Camera2d,
+///         Camera {
+///             order: 1,
+///             ..default()
+///         },
+///         EguiContextMirror(*primary_context),
+///     ));
+/// }
+/// ```
+#[cfg(feature = "render")]
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EguiContextMirror(pub Entity);
+
+/// Shrinks the viewport of the camera this component is added to, so that it covers only the space
+/// that isn't occupied by the panels (e.g. [`egui::SidePanel`], [`egui::TopBottomPanel`]) of an Egui context.
+///
+/// The value is the entity of the camera with the [`EguiContext`], the camera with this component
+/// can't have an Egui context itself. The viewport is updated by [`update_reserved_space_viewports_system`]
+/// after every pass of the context, so a 3D or 2D scene stays centered in the space that's left.
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_egui::{EguiReservedSpace, PrimaryEguiContext};
+/// fn setup_system(mut commands: Commands) {
+///     let egui_camera = commands.spawn((PrimaryEguiContext, Camera2d)).id();
+///     commands.spawn((
+///         Camera3d::default(),
+///         Camera {
+///             order: -1,
+///             ..default()
+///         },
+///         EguiReservedSpace(egui_camera),
+///     ));
+/// }
+/// ```
+#[cfg(feature = "render")]
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EguiReservedSpace(pub Entity);
+
+/// Updates viewports of cameras with the [`EguiReservedSpace`] component.
+///
+/// The viewport matches [`egui::Context::available_rect`], i.e. the context screen rect minus the panels.
+#[cfg(feature = "render")]
+pub fn update_reserved_space_viewports_system(
+    mut contexts: Query<(&mut EguiContext, &EguiContextComputedScaleFactor)>,
+    mut cameras: Query<
+        (&mut bevy_render::camera::Camera, &EguiReservedSpace),
+        Without<EguiContext>,
+    >,
+) {
+    for (mut camera, reserved_space) in cameras.iter_mut() {
+        let Ok((mut context, computed_scale_factor)) = contexts.get_mut(reserved_space.0) else {
+            continue;
+        };
+        let available_rect = context.get_mut().available_rect();
+        if !available_rect.is_finite() {
+            continue;
+        }
+
+        let scale_factor = computed_scale_factor.scale_factor;
+        let min = (helpers::egui_pos2_into_vec2(available_rect.min) * scale_factor)
+            .round()
+            .max(bevy_math::Vec2::ZERO)
+            .as_uvec2();
+        let max = (helpers::egui_pos2_into_vec2(available_rect.max) * scale_factor)
+            .round()
+            .max(bevy_math::Vec2::ZERO)
+            .as_uvec2();
+        // Zero-sized viewports aren't supported by the renderer.
+        if max.x <= min.x || max.y <= min.y {
+            continue;
+        }
+
+        let depth = camera
+            .viewport
+            .as_ref()
+            .map_or(0.0..1.0, |viewport| viewport.depth.clone());
+        let viewport = bevy_render::camera::Viewport {
+            physical_position: min,
+            physical_size: max - min,
+            depth,
+        };
+        if camera.viewport.as_ref() != Some(&viewport) {
+            camera.viewport = Some(viewport);
+        }
+    }
+}
+
 /// The names of `bevy_egui` nodes.
 pub mod node {
     /// The main egui pass.
@@ -926,6 +2594,11 @@ pub enum EguiInputSet {
 pub enum EguiPostUpdateSet {
     /// Ends Egui pass.
     EndPass,
+    /// Runs after Egui passes have ended and before the output is processed. Is empty by default.
+    ///
+    /// Schedule your systems in this set if you need to modify [`EguiFullOutput`]
+    /// (e.g. to filter [`egui::OutputCommand`]s) before it's consumed by [`process_output_system`].
+    TransformOutput,
     /// Processes Egui output, reads paint jobs for the renderer.
     ProcessOutput,
     /// Post-processing of Egui output (updates textures, browser virtual keyboard state, etc).
@@ -939,9 +2612,43 @@ impl Plugin for EguiPlugin {
         app.init_resource::<EguiGlobalSettings>();
         app.init_resource::<ModifierKeysState>();
         app.init_resource::<EguiWantsInput>();
+        app.init_resource::<input::EguiResentEvents<bevy_input::keyboard::KeyboardInput>>();
+        app.init_resource::<input::EguiResentEvents<bevy_input::mouse::MouseButtonInput>>();
         app.init_resource::<WindowToEguiContextMap>();
+        app.init_resource::<EguiFocusCycleContexts>();
+        #[cfg(feature = "debug_overlay")]
+        {
+            app.init_resource::<debug_overlay::EguiDebugOverlay>();
+            app.init_resource::<debug_overlay::EguiDebugOverlayFlag>();
+            app.add_systems(
+                PreUpdate,
+                (
+                    debug_overlay::update_debug_overlay_flag_system
+                        .run_if(resource_changed::<debug_overlay::EguiDebugOverlay>),
+                    debug_overlay::setup_debug_overlay_callbacks_system,
+                )
+                    .in_set(EguiPreUpdateSet::InitContexts),
+            );
+        }
+        #[cfg(feature = "diagnostics")]
+        app.add_systems(
+            PostUpdate,
+            (
+                diagnostics::register_egui_diagnostics_system,
+                diagnostics::measure_egui_diagnostics_system,
+            )
+                .chain()
+                .run_if(resource_exists::<bevy_diagnostic::DiagnosticsStore>)
+                .in_set(EguiPostUpdateSet::PostProcessOutput),
+        );
         app.add_event::<EguiInputEvent>();
+        app.add_event::<EguiUnmappedKeyEvent>();
         app.add_event::<EguiFileDragAndDropEvent>();
+        app.add_event::<EguiScheduleConflictEvent>();
+        app.add_event::<EguiContextWindowClosedEvent>();
+        app.add_event::<output::EguiDragOutEvent>();
+        app.add_event::<output::EguiTextCursorEvent>();
+        app.init_resource::<output::EguiDragOutPayloadTypes>();
 
         #[allow(deprecated)]
         if self.enable_multipass_for_primary_context {
@@ -951,8 +2658,12 @@ impl Plugin for EguiPlugin {
         #[cfg(feature = "render")]
         {
             app.init_resource::<EguiManagedTextures>();
+            app.init_resource::<EguiManagedTexturePartialUpdates>();
             app.init_resource::<EguiUserTextures>();
             app.add_plugins(ExtractResourcePlugin::<EguiUserTextures>::default());
+            #[cfg(feature = "svg")]
+            app.init_resource::<svg::EguiSvgCache>();
+            app.add_plugins(ExtractResourcePlugin::<EguiManagedTexturePartialUpdates>::default());
             app.add_plugins(ExtractResourcePlugin::<
                 render::systems::ExtractedEguiManagedTextures,
             >::default());
@@ -988,6 +2699,7 @@ impl Plugin for EguiPlugin {
             PostUpdate,
             (
                 EguiPostUpdateSet::EndPass,
+                EguiPostUpdateSet::TransformOutput,
                 EguiPostUpdateSet::ProcessOutput,
                 EguiPostUpdateSet::PostProcessOutput,
             )
@@ -998,6 +2710,7 @@ impl Plugin for EguiPlugin {
             PostUpdate,
             (
                 EguiPostUpdateSet::EndPass,
+                EguiPostUpdateSet::TransformOutput,
                 EguiPostUpdateSet::ProcessOutput,
                 EguiPostUpdateSet::PostProcessOutput.before(bevy_a11y::AccessibilitySystem::Update),
             )
@@ -1011,17 +2724,37 @@ impl Plugin for EguiPlugin {
         }
         #[cfg(feature = "render")]
         app.add_systems(
-            PreStartup,
-            (
-                (setup_primary_egui_context_system, ApplyDeferred)
-                    .run_if(|s: Res<EguiGlobalSettings>| s.auto_create_primary_context),
-                update_ui_size_and_scale_system,
-            )
-                .chain()
-                .in_set(EguiStartupSet::InitContexts),
+            PreStartup,
+            (
+                (setup_primary_egui_context_system, ApplyDeferred)
+                    .run_if(|s: Res<EguiGlobalSettings>| s.auto_create_primary_context),
+                update_ui_size_and_scale_system,
+            )
+                .chain()
+                .in_set(EguiStartupSet::InitContexts),
+        );
+
+        // PreUpdate systems.
+        app.add_systems(
+            PreUpdate,
+            (
+                update_offscreen_context_size_and_scale_system,
+                output::setup_drag_out_callbacks_system,
+                setup_scroll_callbacks_system,
+                (
+                    apply_egui_context_options_system,
+                    update_scroll_zoom_modifier_system,
+                )
+                    .chain(),
+                update_embed_viewports_system,
+            )
+                .in_set(EguiPreUpdateSet::InitContexts),
+        );
+        #[cfg(feature = "render")]
+        app.add_systems(
+            PreUpdate,
+            update_image_target_size_and_scale_system.in_set(EguiPreUpdateSet::InitContexts),
         );
-
-        // PreUpdate systems.
         #[cfg(feature = "render")]
         app.add_systems(
             PreUpdate,
@@ -1030,6 +2763,7 @@ impl Plugin for EguiPlugin {
                     .run_if(|s: Res<EguiGlobalSettings>| s.auto_create_primary_context),
                 WindowToEguiContextMap::on_egui_context_added_system,
                 WindowToEguiContextMap::on_egui_context_removed_system,
+                WindowToEguiContextMap::on_window_removed_system,
                 ApplyDeferred,
                 update_ui_size_and_scale_system,
             )
@@ -1055,6 +2789,9 @@ impl Plugin for EguiPlugin {
                     write_window_touch_events_system.run_if(input_system_is_enabled(|s| {
                         s.run_write_window_touch_events_system
                     })),
+                    cycle_focused_non_window_context_system.run_if(
+                        |settings: Res<EguiGlobalSettings>| settings.focus_cycle_shortcut.is_some(),
+                    ),
                 )
                     .in_set(EguiInputSet::FocusContext),
                 (
@@ -1064,9 +2801,13 @@ impl Plugin for EguiPlugin {
                     write_non_window_touch_events_system.run_if(input_system_is_enabled(|s| {
                         s.run_write_non_window_touch_events_system
                     })),
+                    write_touch_inertia_events_system.after(write_non_window_touch_events_system),
                     write_mouse_wheel_events_system.run_if(input_system_is_enabled(|s| {
                         s.run_write_mouse_wheel_events_system
                     })),
+                    write_zoom_gesture_events_system.run_if(input_system_is_enabled(|s| {
+                        s.run_write_zoom_gesture_events_system
+                    })),
                     write_keyboard_input_events_system.run_if(input_system_is_enabled(|s| {
                         s.run_write_keyboard_input_events_system
                     })),
@@ -1088,6 +2829,27 @@ impl Plugin for EguiPlugin {
                 .chain()
                 .in_set(EguiPreUpdateSet::ProcessInput),
         );
+        app.add_systems(
+            PreUpdate,
+            (
+                (
+                    record::replay_egui_input_system
+                        .run_if(resource_exists::<record::EguiInputReplay>),
+                    record::restore_input_after_replay_system,
+                )
+                    .chain()
+                    .in_set(EguiInputSet::ReadBevyEvents),
+                record::record_egui_input_system
+                    .run_if(resource_exists::<record::EguiInputRecorder>)
+                    .before(write_egui_input_system)
+                    .in_set(EguiInputSet::WriteEguiEvents),
+                record::write_replay_time_system
+                    .run_if(resource_exists::<record::EguiInputReplay>)
+                    .after(write_egui_input_system)
+                    .in_set(EguiInputSet::WriteEguiEvents),
+            )
+                .in_set(EguiPreUpdateSet::ProcessInput),
+        );
         app.add_systems(
             PreUpdate,
             begin_pass_system.in_set(EguiPreUpdateSet::BeginPass),
@@ -1168,6 +2930,9 @@ impl Plugin for EguiPlugin {
             (
                 process_output_system,
                 write_egui_wants_input_system,
+                mark_egui_contexts_ready_system,
+                output::write_ime_position_system.after(process_output_system),
+                output::write_drag_out_events_system,
                 #[cfg(any(target_os = "ios", target_os = "android"))]
                 // show the virtual keyboard on mobile devices
                 set_ime_allowed_system,
@@ -1177,14 +2942,31 @@ impl Plugin for EguiPlugin {
         #[cfg(feature = "picking")]
         if app.is_plugin_added::<bevy_picking::PickingPlugin>() {
             app.add_systems(PostUpdate, capture_pointer_input_system);
-        } else {
+        } else if self.warn_on_missing_picking_plugin {
             log::warn!("The `bevy_egui/picking` feature is enabled, but `PickingPlugin` is not added (if you use Bevy's `DefaultPlugins`, make sure the `bevy/bevy_picking` feature is enabled too)");
         }
 
+        #[cfg(feature = "render")]
+        app.add_event::<screenshot::EguiScreenshotRequestEvent>()
+            .add_event::<screenshot::EguiScreenshotReadyEvent>()
+            .add_systems(
+                PostUpdate,
+                (
+                    screenshot::capture_egui_screenshots_system,
+                    screenshot::spawn_egui_screenshot_cameras_system,
+                )
+                    .chain()
+                    .in_set(EguiPostUpdateSet::PostProcessOutput),
+            );
         #[cfg(feature = "render")]
         app.add_systems(
             PostUpdate,
-            update_egui_textures_system.in_set(EguiPostUpdateSet::PostProcessOutput),
+            (
+                update_egui_textures_system,
+                update_premultiplied_user_textures_system,
+                update_reserved_space_viewports_system,
+            )
+                .in_set(EguiPostUpdateSet::PostProcessOutput),
         )
         .add_systems(
             Render,
@@ -1198,7 +2980,13 @@ impl Plugin for EguiPlugin {
             Render,
             render::systems::queue_pipelines_system.in_set(RenderSet::Queue),
         )
-        .add_systems(Last, free_egui_textures_system);
+        .add_systems(
+            Last,
+            (
+                free_egui_textures_system,
+                prune_orphaned_egui_textures_system,
+            ),
+        );
 
         #[cfg(feature = "render")]
         {
@@ -1215,52 +3003,63 @@ impl Plugin for EguiPlugin {
 
             let egui_graph_2d = render::get_egui_graph(render_app);
             let egui_graph_3d = render::get_egui_graph(render_app);
+            let egui_graph_image_targets = render::get_egui_graph(render_app);
             let mut graph = render_app
                 .world_mut()
                 .resource_mut::<bevy_render::render_graph::RenderGraph>();
 
+            // Image targets aren't views of cameras, so their subgraph is run from the root graph,
+            // before cameras, which may display the images.
+            graph.add_sub_graph(render::graph::SubGraphEgui, egui_graph_image_targets);
+            graph.add_node(
+                render::graph::NodeEgui::ImageTargets,
+                render::RunEguiSubgraphOnImageTargetsNode,
+            );
+            graph.add_node_edge(
+                render::graph::NodeEgui::ImageTargets,
+                bevy_render::graph::CameraDriverLabel,
+            );
+
             if let Some(graph_2d) =
                 graph.get_sub_graph_mut(bevy_core_pipeline::core_2d::graph::Core2d)
             {
+                use bevy_core_pipeline::core_2d::graph::Node2d;
                 graph_2d.add_sub_graph(render::graph::SubGraphEgui, egui_graph_2d);
                 graph_2d.add_node(
                     render::graph::NodeEgui::EguiPass,
                     render::RunEguiSubgraphOnEguiViewNode,
                 );
-                graph_2d.add_node_edge(
-                    bevy_core_pipeline::core_2d::graph::Node2d::EndMainPass,
-                    render::graph::NodeEgui::EguiPass,
-                );
-                graph_2d.add_node_edge(
-                    bevy_core_pipeline::core_2d::graph::Node2d::EndMainPassPostProcessing,
-                    render::graph::NodeEgui::EguiPass,
-                );
-                graph_2d.add_node_edge(
-                    render::graph::NodeEgui::EguiPass,
-                    bevy_core_pipeline::core_2d::graph::Node2d::Upscaling,
-                );
+                let (after, before) = match self.pass_placement {
+                    EguiPassPlacement::BeforeTonemapping => {
+                        (Node2d::EndMainPass, Node2d::Tonemapping)
+                    }
+                    EguiPassPlacement::AfterPostProcessing => {
+                        (Node2d::EndMainPassPostProcessing, Node2d::Upscaling)
+                    }
+                };
+                graph_2d.add_node_edge(after, render::graph::NodeEgui::EguiPass);
+                graph_2d.add_node_edge(render::graph::NodeEgui::EguiPass, before);
             }
 
             if let Some(graph_3d) =
                 graph.get_sub_graph_mut(bevy_core_pipeline::core_3d::graph::Core3d)
             {
+                use bevy_core_pipeline::core_3d::graph::Node3d;
                 graph_3d.add_sub_graph(render::graph::SubGraphEgui, egui_graph_3d);
                 graph_3d.add_node(
                     render::graph::NodeEgui::EguiPass,
                     render::RunEguiSubgraphOnEguiViewNode,
                 );
-                graph_3d.add_node_edge(
-                    bevy_core_pipeline::core_3d::graph::Node3d::EndMainPass,
-                    render::graph::NodeEgui::EguiPass,
-                );
-                graph_3d.add_node_edge(
-                    bevy_core_pipeline::core_3d::graph::Node3d::EndMainPassPostProcessing,
-                    render::graph::NodeEgui::EguiPass,
-                );
-                graph_3d.add_node_edge(
-                    render::graph::NodeEgui::EguiPass,
-                    bevy_core_pipeline::core_3d::graph::Node3d::Upscaling,
-                );
+                let (after, before) = match self.pass_placement {
+                    EguiPassPlacement::BeforeTonemapping => {
+                        (Node3d::EndMainPass, Node3d::Tonemapping)
+                    }
+                    EguiPassPlacement::AfterPostProcessing => {
+                        (Node3d::EndMainPassPostProcessing, Node3d::Upscaling)
+                    }
+                };
+                graph_3d.add_node_edge(after, render::graph::NodeEgui::EguiPass);
+                graph_3d.add_node_edge(render::graph::NodeEgui::EguiPass, before);
             }
         }
 
@@ -1286,7 +3085,10 @@ impl Plugin for EguiPlugin {
                     // Seems to be just the set to add/remove nodes, as it'll run before
                     // `RenderSet::ExtractCommands` where render nodes get updated.
                     ExtractSchedule,
-                    render::extract_egui_camera_view_system,
+                    (
+                        render::extract_egui_camera_view_system,
+                        render::extract_egui_image_target_views_system,
+                    ),
                 )
                 .add_systems(
                     Render,
@@ -1297,6 +3099,11 @@ impl Plugin for EguiPlugin {
                     render::systems::prepare_egui_render_target_data_system
                         .in_set(RenderSet::Prepare),
                 )
+                .add_systems(
+                    Render,
+                    render::systems::write_egui_managed_texture_partial_updates_system
+                        .in_set(RenderSet::PrepareResources),
+                )
                 .add_systems(
                     Render,
                     render::systems::queue_bind_groups_system.in_set(RenderSet::Queue),
@@ -1305,25 +3112,42 @@ impl Plugin for EguiPlugin {
                     Render,
                     render::systems::queue_pipelines_system.in_set(RenderSet::Queue),
                 );
+            if self.prewarm_pipelines {
+                render_app.add_systems(
+                    Render,
+                    render::systems::prewarm_pipelines_system.in_set(RenderSet::Queue),
+                );
+            }
 
             // Configure a fixed rendering order between Bevy UI and egui.
             // Otherwise, this order is effectively decided at random on every game startup.
             #[cfg(feature = "bevy_ui")]
-            if bevy_ui_is_enabled {
+            let ui_render_order_edge = {
                 use bevy_render::render_graph::RenderLabel;
-                let mut graph = render_app
-                    .world_mut()
-                    .resource_mut::<bevy_render::render_graph::RenderGraph>();
-                let (below, above) = match self.ui_render_order {
-                    UiRenderOrder::EguiAboveBevyUi => (
+                // The edge goes from the node rendered below to the node rendered above.
+                match self.ui_render_order {
+                    UiRenderOrder::EguiAboveBevyUi => Some((
                         bevy_ui::graph::NodeUi::UiPass.intern(),
                         render::graph::NodeEgui::EguiPass.intern(),
-                    ),
-                    UiRenderOrder::BevyUiAboveEgui => (
+                    )),
+                    UiRenderOrder::BevyUiAboveEgui => Some((
                         render::graph::NodeEgui::EguiPass.intern(),
                         bevy_ui::graph::NodeUi::UiPass.intern(),
-                    ),
-                };
+                    )),
+                    UiRenderOrder::Manual => None,
+                }
+            };
+            #[cfg(feature = "bevy_ui")]
+            if ui_render_order_edge.is_none() {
+                log::debug!("Egui render order is configured manually, not adding a render graph edge between Bevy UI and Egui")
+            } else if self.pass_placement == EguiPassPlacement::BeforeTonemapping {
+                // Bevy UI is rendered after post-processing, so Egui always ends up below it.
+                log::debug!("Egui pass is placed before tonemapping, not applying configured rendering order")
+            } else if let Some((below, above)) = ui_render_order_edge.filter(|_| bevy_ui_is_enabled)
+            {
+                let mut graph = render_app
+                    .world_mut()
+                    .resource_mut::<bevy_render::render_graph::RenderGraph>();
                 if let Some(graph_2d) =
                     graph.get_sub_graph_mut(bevy_core_pipeline::core_2d::graph::Core2d)
                 {
@@ -1372,15 +3196,65 @@ fn input_system_is_enabled(
 #[derive(Resource, Deref, DerefMut, Default)]
 pub struct EguiManagedTextures(pub HashMap<(Entity, u64), EguiManagedTexture>);
 
+#[cfg(feature = "render")]
+impl EguiManagedTextures {
+    /// Lists textures that belong to contexts missing from `live_contexts`.
+    pub fn orphans<'a>(
+        &'a self,
+        live_contexts: &'a HashSet<Entity>,
+    ) -> impl Iterator<Item = (Entity, u64)> + 'a {
+        self.0
+            .keys()
+            .filter(|(entity, _)| !live_contexts.contains(entity))
+            .copied()
+    }
+
+    /// Frees textures that belong to contexts missing from `live_contexts`.
+    ///
+    /// Textures are normally freed when Egui asks for it, but this doesn't happen if a context gets despawned.
+    /// [`prune_orphaned_egui_textures_system`] calls this function whenever a context is removed.
+    pub fn prune_orphans(&mut self, live_contexts: &HashSet<Entity>, assets: &mut Assets<Image>) {
+        self.0.retain(|(entity, _), managed_texture| {
+            let is_live = live_contexts.contains(entity);
+            if !is_live {
+                assets.remove(&managed_texture.handle);
+            }
+            is_live
+        });
+    }
+}
+
 /// Represents a texture allocated and painted by Egui.
 #[cfg(feature = "render")]
 pub struct EguiManagedTexture {
     /// Assets store handle.
+    ///
+    /// The handle stays the same on partial updates. Note that partial updates are written directly
+    /// into the GPU texture, so the main world [`Image`] data may be out of date (use [`EguiManagedTexture::color_image`] instead).
     pub handle: Handle<Image>,
     /// Stored in full so we can do partial updates (which bevy doesn't support).
     pub color_image: egui::ColorImage,
 }
 
+/// Partial updates of [`EguiManagedTextures`] that are written directly into the respective GPU textures.
+///
+/// The resource is populated by [`update_egui_textures_system`] and extracted into the render world.
+#[cfg(feature = "render")]
+#[derive(Resource, Deref, DerefMut, Default, Clone, ExtractResource)]
+pub struct EguiManagedTexturePartialUpdates(pub Vec<EguiManagedTexturePartialUpdate>);
+
+/// Represents a partial update of a texture allocated by Egui.
+#[cfg(feature = "render")]
+#[derive(Clone)]
+pub struct EguiManagedTexturePartialUpdate {
+    /// Assets store handle of the updated texture.
+    pub handle: Handle<Image>,
+    /// Position of the updated region.
+    pub pos: [usize; 2],
+    /// Updated region.
+    pub color_image: egui::ColorImage,
+}
+
 /// Adds bevy_egui components to a first found camera assuming it's a primary one.
 ///
 /// To disable this behavior, set [`EguiGlobalSettings::auto_create_primary_context`] to `false` before you create your first camera.
@@ -1388,7 +3262,14 @@ pub struct EguiManagedTexture {
 #[cfg(feature = "render")]
 pub fn setup_primary_egui_context_system(
     mut commands: Commands,
-    new_cameras: Query<(Entity, Option<&EguiContext>), Added<bevy_render::camera::Camera>>,
+    new_cameras: Query<
+        (
+            Entity,
+            Option<&EguiContext>,
+            Has<PreferredPrimaryEguiCamera>,
+        ),
+        Added<bevy_render::camera::Camera>,
+    >,
     #[cfg(feature = "accesskit_placeholder")] adapters: Option<
         NonSend<bevy_winit::accessibility::AccessKitAdapters>,
     >,
@@ -1398,7 +3279,10 @@ pub fn setup_primary_egui_context_system(
     enable_multipass_for_primary_context: Option<Res<EnableMultipassForPrimaryContext>>,
     mut egui_context_exists: Local<bool>,
 ) -> Result {
-    for (camera_entity, context) in new_cameras {
+    // Cameras with the `PreferredPrimaryEguiCamera` marker go first.
+    let preferred_cameras = new_cameras.iter().filter(|(_, _, preferred)| *preferred);
+    let other_cameras = new_cameras.iter().filter(|(_, _, preferred)| !*preferred);
+    for (camera_entity, context, _) in preferred_cameras.chain(other_cameras) {
         if context.is_some() || *egui_context_exists {
             *egui_context_exists = true;
             return Ok(());
@@ -1447,11 +3331,45 @@ impl EguiClipboard {
         self.get_text_impl()
     }
 
+    /// Gets clipboard image content. Returns [`None`] if the clipboard doesn't contain an image,
+    /// or if clipboard provider is unavailable or returns an error.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[must_use]
+    pub fn get_image(&mut self) -> Option<egui::ColorImage> {
+        self.get_image_impl()
+    }
+
+    /// Returns the kind of the clipboard contents that were last read, which can be used to tell
+    /// an empty text from a pasted image.
+    ///
+    /// On web, it reflects the last `paste` event. On other platforms, it reflects the last
+    /// [`EguiClipboard::get_text`] or [`EguiClipboard::get_image`] call: reading text from a clipboard
+    /// that contains only an image reports [`EguiClipboardContentKind::Empty`], call [`EguiClipboard::get_image`]
+    /// to check for an image.
+    /// Returns [`None`] if the clipboard hasn't been read yet, or if the clipboard provider returned an error.
+    #[must_use]
+    pub fn last_event_kind(&self) -> Option<EguiClipboardContentKind> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.last_event_kind
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.clipboard.last_event_kind()
+        }
+    }
+
     /// Places an image to the clipboard.
     pub fn set_image(&mut self, image: &egui::ColorImage) {
         self.set_image_impl(image);
     }
 
+    /// Sets the kind of the contents of the last "Paste" event.
+    #[cfg(target_arch = "wasm32")]
+    pub fn set_last_event_kind(&mut self, kind: EguiClipboardContentKind) {
+        self.clipboard.set_last_event_kind(kind);
+    }
+
     /// Receives a clipboard event sent by the `copy`/`cut`/`paste` listeners.
     #[cfg(target_arch = "wasm32")]
     pub fn try_receive_clipboard_event(&self) -> Option<web_clipboard::WebClipboardEvent> {
@@ -1474,15 +3392,29 @@ impl EguiClipboard {
 
     #[cfg(not(target_arch = "wasm32"))]
     fn get_text_impl(&mut self) -> Option<String> {
-        if let Some(mut clipboard) = self.get() {
-            match clipboard.get_text() {
-                Ok(contents) => return Some(contents),
+        let (contents, last_event_kind) = match self.get() {
+            Some(mut clipboard) => match clipboard.get_text() {
+                Ok(contents) => {
+                    let kind = if contents.is_empty() {
+                        EguiClipboardContentKind::Empty
+                    } else {
+                        EguiClipboardContentKind::Text
+                    };
+                    (Some(contents), Some(kind))
+                }
                 // We don't want to spam with this error as it usually means that the clipboard is either empty or has an incompatible format (e.g. image).
-                Err(arboard::Error::ContentNotAvailable) => return Some("".to_string()),
-                Err(err) => log::error!("Failed to get clipboard contents: {:?}", err),
-            }
+                Err(arboard::Error::ContentNotAvailable) => {
+                    (Some("".to_string()), Some(EguiClipboardContentKind::Empty))
+                }
+                Err(err) => {
+                    log::error!("Failed to get clipboard contents: {:?}", err);
+                    (None, None)
+                }
+            },
+            None => (None, None),
         };
-        None
+        self.last_event_kind = last_event_kind;
+        contents
     }
 
     #[cfg(target_arch = "wasm32")]
@@ -1491,6 +3423,31 @@ impl EguiClipboard {
         self.clipboard.get_text()
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
+    fn get_image_impl(&mut self) -> Option<egui::ColorImage> {
+        let (image, last_event_kind) = match self.get() {
+            Some(mut clipboard) => match clipboard.get_image() {
+                Ok(image) => (
+                    Some(egui::ColorImage::from_rgba_unmultiplied(
+                        [image.width, image.height],
+                        &image.bytes,
+                    )),
+                    Some(EguiClipboardContentKind::Image),
+                ),
+                Err(arboard::Error::ContentNotAvailable) => {
+                    (None, Some(EguiClipboardContentKind::Empty))
+                }
+                Err(err) => {
+                    log::error!("Failed to get clipboard contents: {:?}", err);
+                    (None, None)
+                }
+            },
+            None => (None, None),
+        };
+        self.last_event_kind = last_event_kind;
+        image
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     fn set_image_impl(&mut self, image: &egui::ColorImage) {
         if let Some(mut clipboard) = self.get() {
@@ -1532,7 +3489,7 @@ pub const PICKING_ORDER: f32 = 1_000_000.0;
 /// Captures pointers on Egui windows for [`bevy_picking`].
 #[cfg(feature = "picking")]
 pub fn capture_pointer_input_system(
-    pointers: Query<(&PointerId, &PointerLocation)>,
+    pointers: Query<(&PointerId, &PointerLocation, Option<&PointerPress>)>,
     mut egui_context: Query<(
         Entity,
         &mut EguiContext,
@@ -1542,13 +3499,15 @@ pub fn capture_pointer_input_system(
     mut output: EventWriter<PointerHits>,
     window_to_egui_context_map: Res<WindowToEguiContextMap>,
 ) {
+    use bevy_input::mouse::MouseButton;
     use helpers::QueryHelper;
 
-    for (pointer, location) in pointers
+    for (pointer, location, press) in pointers
         .iter()
-        .filter_map(|(i, p)| p.location.as_ref().map(|l| (i, l)))
+        .filter_map(|(i, p, press)| p.location.as_ref().map(|l| (i, l, press)))
     {
         if let NormalizedRenderTarget::Window(window) = location.target {
+            let mut capturing_contexts = Vec::new();
             for window_context_entity in window_to_egui_context_map
                 .window_to_contexts
                 .get(&window.entity())
@@ -1567,15 +3526,43 @@ pub fn capture_pointer_input_system(
                     continue;
                 }
 
-                if settings.capture_pointer_input && ctx.get_mut().wants_pointer_input() {
-                    let entry = (entity, HitData::new(entity, 0.0, None, None));
-                    output.write(PointerHits::new(
-                        *pointer,
-                        Vec::from([entry]),
-                        PICKING_ORDER,
-                    ));
+                // Presses of the buttons that the context doesn't capture reach the entities below.
+                let captures_pressed_buttons = press.map_or(true, |press| {
+                    [
+                        (press.is_primary_pressed(), MouseButton::Left),
+                        (press.is_secondary_pressed(), MouseButton::Right),
+                        (press.is_middle_pressed(), MouseButton::Middle),
+                    ]
+                    .into_iter()
+                    .all(|(pressed, button)| !pressed || settings.captures_button(button))
+                });
+                if settings.capture_pointer_input
+                    && captures_pressed_buttons
+                    && ctx.get_mut().wants_pointer_input()
+                {
+                    capturing_contexts.push((entity, settings.pointer_capture_priority));
                 }
             }
+
+            // Only the contexts with the highest priority capture the pointer.
+            let Some(max_priority) = capturing_contexts
+                .iter()
+                .map(|(_, priority)| *priority)
+                .max()
+            else {
+                continue;
+            };
+            for (entity, _) in capturing_contexts
+                .into_iter()
+                .filter(|(_, priority)| *priority == max_priority)
+            {
+                let entry = (entity, HitData::new(entity, 0.0, None, None));
+                output.write(PointerHits::new(
+                    *pointer,
+                    Vec::from([entry]),
+                    PICKING_ORDER,
+                ));
+            }
         }
     }
 }
@@ -1583,11 +3570,22 @@ pub fn capture_pointer_input_system(
 /// Updates textures painted by Egui.
 #[cfg(feature = "render")]
 pub fn update_egui_textures_system(
-    mut egui_render_output: Query<(Entity, &EguiRenderOutput)>,
+    mut egui_render_output: Query<(Entity, &EguiRenderOutput, Option<&EguiContextSettings>)>,
     mut egui_managed_textures: ResMut<EguiManagedTextures>,
+    mut partial_updates: ResMut<EguiManagedTexturePartialUpdates>,
     mut image_assets: ResMut<Assets<Image>>,
 ) {
-    for (entity, egui_render_output) in egui_render_output.iter_mut() {
+    // Avoid triggering change detection (and extraction) if there's nothing to clear.
+    if !partial_updates.is_empty() {
+        partial_updates.clear();
+    }
+    // Textures that got (re)allocated this frame aren't uploaded to GPU yet,
+    // so their partial updates can't be written directly.
+    let mut allocated_textures = HashSet::<(Entity, u64)>::default();
+
+    for (entity, egui_render_output, settings) in egui_render_output.iter_mut() {
+        // Upscaled contexts keep their pixels sharp.
+        let is_upscaled = settings.is_some_and(|settings| settings.fixed_logical_size.is_some());
         for (texture_id, image_delta) in &egui_render_output.textures_delta.set {
             let color_image = render::as_color_image(&image_delta.image);
 
@@ -1596,25 +3594,47 @@ pub fn update_egui_textures_system(
                 egui::TextureId::User(_) => continue,
             };
 
+            let texture_options = if is_upscaled {
+                egui::TextureOptions {
+                    magnification: egui::TextureFilter::Nearest,
+                    minification: egui::TextureFilter::Nearest,
+                    ..image_delta.options
+                }
+            } else {
+                image_delta.options
+            };
             let sampler = ImageSampler::Descriptor(render::texture_options_as_sampler_descriptor(
-                &image_delta.options,
+                &texture_options,
             ));
             if let Some(pos) = image_delta.pos {
                 // Partial update.
-                if let Some(managed_texture) = egui_managed_textures.get_mut(&(entity, texture_id))
-                {
-                    // TODO: when bevy supports it, only update the part of the texture that changes.
-                    update_image_rect(&mut managed_texture.color_image, pos, &color_image);
-                    let image =
-                        render::color_image_as_bevy_image(&managed_texture.color_image, sampler);
-                    managed_texture.handle = image_assets.add(image);
-                } else {
+                let Some(managed_texture) = egui_managed_textures.get_mut(&(entity, texture_id))
+                else {
                     log::warn!("Partial update of a missing texture (id: {:?})", texture_id);
+                    continue;
+                };
+
+                update_image_rect(&mut managed_texture.color_image, pos, &color_image);
+                if allocated_textures.contains(&(entity, texture_id)) {
+                    // Replace the image data, keeping the handle.
+                    if let Some(image) = image_assets.get_mut(&managed_texture.handle) {
+                        *image = render::color_image_as_bevy_image(
+                            &managed_texture.color_image,
+                            sampler,
+                        );
+                    }
+                } else {
+                    partial_updates.push(EguiManagedTexturePartialUpdate {
+                        handle: managed_texture.handle.clone(),
+                        pos,
+                        color_image,
+                    });
                 }
             } else {
                 // Full update.
                 let image = render::color_image_as_bevy_image(&color_image, sampler);
                 let handle = image_assets.add(image);
+                allocated_textures.insert((entity, texture_id));
                 egui_managed_textures.insert(
                     (entity, texture_id),
                     EguiManagedTexture {
@@ -1665,6 +3685,100 @@ pub fn free_egui_textures_system(
     }
 }
 
+/// Creates (and updates) premultiplied copies of user textures added with [`EguiUserTextureAlpha::PremultiplyOnUpload`].
+#[cfg(feature = "render")]
+pub fn update_premultiplied_user_textures_system(
+    mut egui_user_textures: ResMut<EguiUserTextures>,
+    mut image_assets: ResMut<Assets<Image>>,
+    mut image_events: EventReader<AssetEvent<Image>>,
+) {
+    let changed_images = image_events
+        .read()
+        .filter_map(|image_event| match image_event {
+            AssetEvent::Added { id }
+            | AssetEvent::Modified { id }
+            | AssetEvent::LoadedWithDependencies { id } => Some(*id),
+            _ => None,
+        })
+        .collect::<HashSet<_>>();
+
+    // Avoid triggering change detection (and extraction) if there's nothing to update.
+    let user_textures = egui_user_textures.bypass_change_detection();
+    // Modified images get another chance (e.g. their data may have been set).
+    user_textures
+        .rejected_premultiplied_images
+        .retain(|image| !changed_images.contains(image));
+    let outdated_images = user_textures
+        .textures
+        .iter()
+        .filter(|(_, id)| {
+            user_textures.alpha_mode(**id) == EguiUserTextureAlpha::PremultiplyOnUpload
+        })
+        .map(|(image, _)| image.id())
+        .filter(|image| {
+            changed_images.contains(image)
+                || !(user_textures.premultiplied_copies.contains_key(image)
+                    || user_textures.rejected_premultiplied_images.contains(image))
+        })
+        .collect::<Vec<_>>();
+
+    for image in outdated_images {
+        // Not loaded yet.
+        let Some(source_image) = image_assets.get(image) else {
+            continue;
+        };
+        let format = source_image.texture_descriptor.format;
+        if !render::can_premultiply_image_alpha(format) {
+            log::warn_once!(
+                "Can't premultiply a user texture with the {format:?} format, it's treated as having straight alpha"
+            );
+            egui_user_textures
+                .bypass_change_detection()
+                .rejected_premultiplied_images
+                .insert(image);
+            continue;
+        }
+        if source_image.data.is_none() {
+            log::warn_once!(
+                "Can't premultiply a user texture without data in the main world (see `RenderAssetUsages::MAIN_WORLD`), it's treated as having straight alpha"
+            );
+            egui_user_textures
+                .bypass_change_detection()
+                .rejected_premultiplied_images
+                .insert(image);
+            continue;
+        }
+        let mut premultiplied_image = source_image.clone();
+        render::premultiply_image_alpha(&mut premultiplied_image);
+        match egui_user_textures.premultiplied_copies.get(&image) {
+            Some(copy) => {
+                if let Some(copy_image) = image_assets.get_mut(copy) {
+                    *copy_image = premultiplied_image;
+                }
+            }
+            None => {
+                let copy = image_assets.add(premultiplied_image);
+                egui_user_textures.premultiplied_copies.insert(image, copy);
+            }
+        }
+    }
+}
+
+/// Frees [`EguiManagedTextures`] of removed contexts.
+#[cfg(feature = "render")]
+pub fn prune_orphaned_egui_textures_system(
+    mut removed_contexts: RemovedComponents<EguiContext>,
+    contexts: Query<Entity, With<EguiContext>>,
+    mut egui_managed_textures: ResMut<EguiManagedTextures>,
+    mut image_assets: ResMut<Assets<Image>>,
+) {
+    if removed_contexts.read().count() == 0 {
+        return;
+    }
+    let live_contexts = contexts.iter().collect::<HashSet<_>>();
+    egui_managed_textures.prune_orphans(&live_contexts, &mut image_assets);
+}
+
 /// Helper function for outputting a String from a JsValue
 #[cfg(target_arch = "wasm32")]
 pub fn string_from_js_value(value: &JsValue) -> String {
@@ -1727,34 +3841,210 @@ impl SubscribedEvents {
 #[allow(missing_docs)]
 #[cfg(feature = "render")]
 pub struct UpdateUiSizeAndScaleQuery {
+    entity: Entity,
     ctx: &'static mut EguiContext,
     egui_input: &'static mut EguiInput,
     egui_settings: &'static EguiContextSettings,
+    computed_scale_factor: &'static mut EguiContextComputedScaleFactor,
     camera: &'static bevy_render::camera::Camera,
 }
 
 #[cfg(feature = "render")]
-/// Updates UI [`egui::RawInput::screen_rect`] and calls [`egui::Context::set_pixels_per_point`].
-pub fn update_ui_size_and_scale_system(mut contexts: Query<UpdateUiSizeAndScaleQuery>) {
+/// Updates UI [`egui::RawInput::screen_rect`], [`egui::RawInput::max_texture_side`], [`egui::ViewportInfo`]
+/// and calls [`egui::Context::set_pixels_per_point`].
+pub fn update_ui_size_and_scale_system(
+    mut contexts: Query<UpdateUiSizeAndScaleQuery>,
+    windows: Query<&bevy_window::Window>,
+    monitors: Query<&bevy_window::Monitor>,
+    window_to_egui_context_map: Res<WindowToEguiContextMap>,
+    egui_global_settings: Res<EguiGlobalSettings>,
+    render_device: Option<Res<bevy_render::renderer::RenderDevice>>,
+) {
+    // WebGL2 guarantees at least 2048, which is what we fall back to if the device isn't available.
+    let default_max_texture_side = render_device
+        .map(|render_device| render_device.limits().max_texture_dimension_2d as usize)
+        .or(cfg!(target_arch = "wasm32").then_some(2048));
+
     for mut context in contexts.iter_mut() {
+        context.egui_input.max_texture_side = context
+            .egui_settings
+            .max_texture_side
+            .or(default_max_texture_side);
+
         let Some((scale_factor, viewport_rect)) = context
             .camera
             .target_scaling_factor()
-            .map(|scale_factor| scale_factor * context.egui_settings.scale_factor)
+            .map(|scale_factor| {
+                context.egui_settings.clamp_pixels_per_point(
+                    scale_factor
+                        * context.egui_settings.scale_factor
+                        * egui_global_settings.global_scale_factor,
+                )
+            })
             .zip(context.camera.physical_viewport_rect())
         else {
             continue;
         };
 
+        let (pixels_per_point, scale_factor, logical_size) =
+            match context.egui_settings.fixed_logical_size {
+                Some(size) if size.x > 0 && size.y > 0 => {
+                    // Egui works with one pixel per point, and the output gets upscaled to the viewport.
+                    let upscale = (viewport_rect.size().as_vec2() / size.as_vec2()).min_element();
+                    (1.0, upscale, Some(size.as_vec2()))
+                }
+                _ => (scale_factor, scale_factor, None),
+            };
+
+        let min = viewport_rect.min.as_vec2() / scale_factor;
+        let max = match logical_size {
+            Some(logical_size) => min + logical_size,
+            None => viewport_rect.max.as_vec2() / scale_factor,
+        };
         let viewport_rect = egui::Rect {
-            min: helpers::vec2_into_egui_pos2(viewport_rect.min.as_vec2() / scale_factor),
-            max: helpers::vec2_into_egui_pos2(viewport_rect.max.as_vec2() / scale_factor),
+            min: helpers::vec2_into_egui_pos2(min),
+            max: helpers::vec2_into_egui_pos2(max),
         };
         if viewport_rect.width() < 1.0 || viewport_rect.height() < 1.0 {
             continue;
         }
         context.egui_input.screen_rect = Some(viewport_rect);
-        context.ctx.get_mut().set_pixels_per_point(scale_factor);
+        context.ctx.get_mut().set_pixels_per_point(pixels_per_point);
+        context
+            .computed_scale_factor
+            .set_if_neq(EguiContextComputedScaleFactor { scale_factor });
+
+        let Some(window) = window_to_egui_context_map
+            .context_to_window
+            .get(&context.entity)
+            .and_then(|window_entity| windows.get(*window_entity).ok())
+        else {
+            continue;
+        };
+        let viewport_id = context.egui_input.viewport_id;
+        let viewport_info = context.egui_input.viewports.entry(viewport_id).or_default();
+        update_viewport_info(viewport_info, window, &monitors, scale_factor);
+    }
+}
+
+/// Fills [`egui::ViewportInfo`] from a window.
+///
+/// Bevy doesn't track whether a window is minimized or maximized, and it doesn't expose the size of
+/// window decorations, so [`egui::ViewportInfo::minimized`], [`egui::ViewportInfo::maximized`]
+/// and [`egui::ViewportInfo::outer_rect`] are left unset.
+#[cfg(feature = "render")]
+fn update_viewport_info(
+    viewport_info: &mut egui::ViewportInfo,
+    window: &bevy_window::Window,
+    monitors: &Query<&bevy_window::Monitor>,
+    scale_factor: f32,
+) {
+    let window_position = match window.position {
+        bevy_window::WindowPosition::At(position) => Some(position),
+        _ => None,
+    };
+    let inner_size = window.resolution.physical_size().as_vec2() / scale_factor;
+
+    viewport_info.title = Some(window.title.clone());
+    viewport_info.native_pixels_per_point = Some(window.resolution.scale_factor());
+    viewport_info.inner_rect = window_position.map(|position| {
+        egui::Rect::from_min_size(
+            helpers::vec2_into_egui_pos2(position.as_vec2() / scale_factor),
+            helpers::vec2_into_egui_vec2(inner_size),
+        )
+    });
+    viewport_info.focused = Some(window.focused);
+    viewport_info.fullscreen = Some(!matches!(window.mode, bevy_window::WindowMode::Windowed));
+    // Picks the monitor that contains the window position, or the only monitor if there's just one.
+    let monitor = monitors
+        .iter()
+        .find(|monitor| {
+            window_position.is_some_and(|position| {
+                let min = monitor.physical_position;
+                let max = min
+                    + bevy_math::IVec2::new(
+                        monitor.physical_width as i32,
+                        monitor.physical_height as i32,
+                    );
+                position.cmpge(min).all() && position.cmplt(max).all()
+            })
+        })
+        .or_else(|| monitors.single().ok());
+    viewport_info.monitor_size = monitor.map(|monitor| {
+        helpers::vec2_into_egui_vec2(
+            bevy_math::UVec2::new(monitor.physical_width, monitor.physical_height).as_vec2()
+                / scale_factor,
+        )
+    });
+}
+
+/// Updates [`egui::RawInput::screen_rect`] and calls [`egui::Context::set_pixels_per_point`] for contexts
+/// with the [`EguiOffscreenContext`] component.
+pub fn update_offscreen_context_size_and_scale_system(
+    mut contexts: Query<(
+        &mut EguiContext,
+        &mut EguiInput,
+        &EguiContextSettings,
+        &mut EguiContextComputedScaleFactor,
+        &EguiOffscreenContext,
+    )>,
+    egui_global_settings: Res<EguiGlobalSettings>,
+) {
+    for (mut ctx, mut egui_input, egui_settings, mut computed_scale_factor, offscreen_context) in
+        contexts.iter_mut()
+    {
+        if offscreen_context.size.x < 1.0 || offscreen_context.size.y < 1.0 {
+            continue;
+        }
+        egui_input.screen_rect = Some(egui::Rect::from_min_size(
+            egui::Pos2::ZERO,
+            helpers::vec2_into_egui_vec2(offscreen_context.size),
+        ));
+        let scale_factor = egui_settings.clamp_pixels_per_point(
+            offscreen_context.scale_factor
+                * egui_settings.scale_factor
+                * egui_global_settings.global_scale_factor,
+        );
+        ctx.get_mut().set_pixels_per_point(scale_factor);
+        computed_scale_factor.set_if_neq(EguiContextComputedScaleFactor { scale_factor });
+    }
+}
+
+/// Updates [`egui::RawInput::screen_rect`] and calls [`egui::Context::set_pixels_per_point`] for contexts
+/// with the [`EguiImageTarget`] component.
+#[cfg(feature = "render")]
+pub fn update_image_target_size_and_scale_system(
+    mut contexts: Query<(
+        &mut EguiContext,
+        &mut EguiInput,
+        &EguiContextSettings,
+        &mut EguiContextComputedScaleFactor,
+        &EguiImageTarget,
+    )>,
+    egui_global_settings: Res<EguiGlobalSettings>,
+    images: Res<Assets<Image>>,
+) {
+    for (mut ctx, mut egui_input, egui_settings, mut computed_scale_factor, image_target) in
+        contexts.iter_mut()
+    {
+        let Some(image) = images.get(&image_target.image) else {
+            continue;
+        };
+        let scale_factor = egui_settings.clamp_pixels_per_point(
+            image_target.scale_factor
+                * egui_settings.scale_factor
+                * egui_global_settings.global_scale_factor,
+        );
+        let size = image.size().as_vec2() / scale_factor;
+        if size.x < 1.0 || size.y < 1.0 {
+            continue;
+        }
+        egui_input.screen_rect = Some(egui::Rect::from_min_size(
+            egui::Pos2::ZERO,
+            helpers::vec2_into_egui_vec2(size),
+        ));
+        ctx.get_mut().set_pixels_per_point(scale_factor);
+        computed_scale_factor.set_if_neq(EguiContextComputedScaleFactor { scale_factor });
     }
 }
 
@@ -1820,11 +4110,28 @@ pub struct MultiPassEguiQuery {
     settings: &'static EguiContextSettings,
 }
 
+/// Is sent by [`run_egui_context_pass_loop_system`] when an Egui context is skipped because
+/// its [`EguiMultipassSchedule`] is already used by another context.
+#[derive(Event, BufferedEvent, Clone, Debug)]
+pub struct EguiScheduleConflictEvent {
+    /// The skipped context.
+    pub entity: Entity,
+    /// The schedule that is used by more than one context.
+    pub schedule: InternedScheduleLabel,
+}
+
 /// Runs Egui contexts with the [`EguiMultipassSchedule`] component. If there are no contexts with
 /// this component, runs the [`EguiPrimaryContextPass`] schedule once independently.
-pub fn run_egui_context_pass_loop_system(world: &mut World) {
+pub fn run_egui_context_pass_loop_system(
+    world: &mut World,
+    mut reported_conflicts: Local<HashSet<Entity>>,
+) {
     let mut contexts_query = world.query::<MultiPassEguiQuery>();
     let mut used_schedules = HashSet::<InternedScheduleLabel>::default();
+    let mut conflicting_contexts = HashSet::<Entity>::default();
+    let panic_on_schedule_conflict = world
+        .get_resource::<EguiGlobalSettings>()
+        .is_some_and(|settings| settings.panic_on_schedule_conflict);
 
     let mut multipass_contexts: Vec<_> = contexts_query
         .iter_mut(world)
@@ -1846,7 +4153,29 @@ pub fn run_egui_context_pass_loop_system(world: &mut World) {
         &mut multipass_contexts
     {
         if !used_schedules.insert(*multipass_schedule) {
-            panic!("Each Egui context running in the multi-pass mode must have a unique schedule (attempted to reuse schedule {multipass_schedule:?})");
+            if panic_on_schedule_conflict {
+                panic!("Each Egui context running in the multi-pass mode must have a unique schedule (attempted to reuse schedule {multipass_schedule:?})");
+            }
+            conflicting_contexts.insert(*entity);
+            if reported_conflicts.insert(*entity) {
+                log::error!("Each Egui context running in the multi-pass mode must have a unique schedule, skipping context {entity} (attempted to reuse schedule {multipass_schedule:?})");
+            }
+            world.write_event(EguiScheduleConflictEvent {
+                entity: *entity,
+                schedule: *multipass_schedule,
+            });
+            // Keep the persistent state (screen rect, modifiers, focus, etc.) for when the conflict is resolved,
+            // but drop the events of the skipped pass, so that they don't pile up and get replayed as stale input.
+            **contexts_query
+                .get_mut(world, *entity)
+                .expect("previously queried context")
+                .input = egui::RawInput {
+                events: Vec::new(),
+                hovered_files: Vec::new(),
+                dropped_files: Vec::new(),
+                ..std::mem::take(input)
+            };
+            continue;
         }
 
         let output = ctx.run(input.take(), |_| {
@@ -1858,6 +4187,8 @@ pub fn run_egui_context_pass_loop_system(world: &mut World) {
             .expect("previously queried context")
             .output = Some(output);
     }
+    // Report the contexts again if they start conflicting after the conflict was resolved.
+    reported_conflicts.retain(|entity| conflicting_contexts.contains(entity));
 
     // If Egui's running in the single-pass mode and a user placed all the UI systems in `EguiContextPass`,
     // we want to run the schedule just once.
@@ -1868,8 +4199,8 @@ pub fn run_egui_context_pass_loop_system(world: &mut World) {
         .next()
         .is_none()
     {
-        // Silly control flow to test that we still have a context. Attempting to run the schedule
-        // when a user has closed a window will result in a panic.
+        // The primary context may be gone, e.g. if its window was closed (see `EguiContextWindowClosedEvent`).
+        // Attempting to run the schedule without a context will result in a panic.
         return;
     }
     if !used_schedules.contains(&ScheduleLabel::intern(&EguiPrimaryContextPass)) {
@@ -1900,4 +4231,74 @@ mod tests {
     fn test_readme_deps() {
         version_sync::assert_markdown_deps_updated!("README.md");
     }
+
+    #[cfg(feature = "render")]
+    #[test]
+    fn test_add_image_in_range_reuses_freed_ids() {
+        use super::EguiUserTextures;
+        use bevy_asset::Assets;
+        use bevy_image::Image;
+
+        let images = Assets::<Image>::default();
+        let mut user_textures = EguiUserTextures::default();
+        assert!(user_textures.reserve_range(10, 3));
+
+        let handles = [(); 5].map(|_| images.reserve_handle());
+        for (handle, id) in handles.iter().zip(10..13) {
+            assert_eq!(
+                user_textures.add_image_in_range(handle.clone(), 10),
+                Some(egui::TextureId::User(id))
+            );
+        }
+        assert_eq!(
+            user_textures.add_image_in_range(handles[3].clone(), 10),
+            None
+        );
+
+        assert_eq!(
+            user_textures.remove_image(&handles[1]),
+            Some(egui::TextureId::User(11))
+        );
+        assert_eq!(
+            user_textures.add_image_in_range(handles[3].clone(), 10),
+            Some(egui::TextureId::User(11))
+        );
+        assert_eq!(
+            user_textures.add_image_in_range(handles[4].clone(), 10),
+            None
+        );
+    }
+
+    #[cfg(feature = "render")]
+    #[test]
+    fn test_reserve_range_skips_next_fresh_id() {
+        use super::EguiUserTextures;
+        use bevy_asset::Assets;
+        use bevy_image::Image;
+
+        let images = Assets::<Image>::default();
+        let mut user_textures = EguiUserTextures::default();
+        let handles = [(); 4].map(|_| images.reserve_handle());
+        assert_eq!(
+            user_textures.add_image(handles[0].clone()),
+            egui::TextureId::User(0)
+        );
+
+        // The range contains the id `add_image` would return next.
+        assert!(user_textures.reserve_range(1, 2));
+        assert!(!user_textures.reserve_range(2, 2));
+        assert!(!user_textures.reserve_range(0, 1));
+        assert_eq!(
+            user_textures.add_image(handles[1].clone()),
+            egui::TextureId::User(3)
+        );
+        assert_eq!(
+            user_textures.add_image_in_range(handles[2].clone(), 1),
+            Some(egui::TextureId::User(1))
+        );
+        assert_eq!(
+            user_textures.add_image(handles[3].clone()),
+            egui::TextureId::User(4)
+        );
+    }
 }
@@ -1,15 +1,113 @@
+#[cfg(feature = "debug_overlay")]
+use crate::debug_overlay::EguiDebugOverlay;
 use crate::{
-    helpers, input::WindowToEguiContextMap, EguiContext, EguiContextSettings, EguiFullOutput,
-    EguiGlobalSettings, EguiOutput, EguiRenderOutput,
+    helpers, input::WindowToEguiContextMap, EguiContext, EguiContextClipboard,
+    EguiContextComputedScaleFactor, EguiContextSettings, EguiFullOutput, EguiGlobalSettings,
+    EguiOffscreenContext, EguiOutput, EguiRenderOutput,
 };
+use bevy_app::App;
 use bevy_ecs::{
     entity::Entity,
-    event::EventWriter,
+    event::{BufferedEvent, Event, EventWriter},
+    query::{Added, Has},
+    resource::Resource,
     system::{Commands, Local, Query, Res},
 };
 use bevy_platform::collections::HashMap;
 use bevy_window::RequestRedraw;
 use bevy_winit::cursor::CursorIcon;
+use std::{
+    any::{Any, TypeId},
+    sync::{Arc, Mutex},
+};
+
+/// Overrides the [`CursorIcon`] that `bevy_egui` sets for Egui cursor icons.
+///
+/// If an Egui cursor icon has no override, it's mapped to a system cursor with
+/// [`helpers::egui_to_winit_cursor_icon`], falling back to [`bevy_window::SystemCursorIcon::Default`].
+/// Insert this resource if some system cursors aren't well-supported on your target platform,
+/// or if you want to use custom image cursors:
+///
+/// ```rust
+/// # use bevy::{prelude::*, window::SystemCursorIcon, winit::cursor::CursorIcon};
+/// # use bevy_egui::{egui, output::EguiCursorIcons};
+/// fn setup_system(mut commands: Commands) {
+///     let mut cursor_icons = EguiCursorIcons::default();
+///     cursor_icons.insert(
+///         egui::CursorIcon::ResizeNeSw,
+///         CursorIcon::System(SystemCursorIcon::Move),
+///     );
+///     commands.insert_resource(cursor_icons);
+/// }
+/// ```
+#[derive(Resource, Clone, Debug, Default)]
+pub struct EguiCursorIcons(pub HashMap<egui::CursorIcon, CursorIcon>);
+
+impl EguiCursorIcons {
+    /// Overrides the cursor icon for an Egui cursor icon.
+    pub fn insert(&mut self, egui_cursor_icon: egui::CursorIcon, cursor_icon: CursorIcon) {
+        self.0.insert(egui_cursor_icon, cursor_icon);
+    }
+
+    /// Returns the cursor icon that `bevy_egui` sets for an Egui cursor icon.
+    pub fn get(&self, egui_cursor_icon: egui::CursorIcon) -> CursorIcon {
+        self.0
+            .get(&egui_cursor_icon)
+            .cloned()
+            .unwrap_or_else(|| default_cursor_icon(egui_cursor_icon))
+    }
+}
+
+/// Filters text that Egui copies to the clipboard, e.g. to strip or transform sensitive content.
+///
+/// The filter is applied to [`egui::OutputCommand::CopyText`] commands before the text is written
+/// to [`crate::EguiClipboard`] (or [`EguiContextClipboard`]). Returning `None` suppresses the clipboard write.
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_egui::output::EguiClipboardFilter;
+/// fn setup_system(mut commands: Commands) {
+///     commands.insert_resource(EguiClipboardFilter::new(|text| {
+///         (!text.contains("password")).then(|| text.trim().to_string())
+///     }));
+/// }
+/// ```
+#[derive(Resource)]
+pub struct EguiClipboardFilter(pub Box<dyn Fn(&str) -> Option<String> + Send + Sync>);
+
+impl EguiClipboardFilter {
+    /// Creates a filter from a function.
+    pub fn new(filter: impl Fn(&str) -> Option<String> + Send + Sync + 'static) -> Self {
+        Self(Box::new(filter))
+    }
+
+    /// Applies the filter to copied text.
+    pub fn apply(&self, text: &str) -> Option<String> {
+        (self.0)(text)
+    }
+}
+
+fn default_cursor_icon(egui_cursor_icon: egui::CursorIcon) -> CursorIcon {
+    CursorIcon::System(
+        helpers::egui_to_winit_cursor_icon(egui_cursor_icon)
+            .unwrap_or(bevy_window::SystemCursorIcon::Default),
+    )
+}
+
+/// Is sent when the text cursor or the selection of a focused Egui text field changes,
+/// e.g. to position an on-screen keyboard or a selection-aware toolbar.
+///
+/// The event is also sent with `cursor_rect: None` when a text field loses focus.
+#[derive(Event, BufferedEvent, Clone, Debug, PartialEq)]
+pub struct EguiTextCursorEvent {
+    /// The context with the text field.
+    pub context: Entity,
+    /// The text cursor rectangle in Egui points ([`egui::output::IMEOutput::cursor_rect`]),
+    /// is `None` if no text field is focused.
+    pub cursor_rect: Option<egui::Rect>,
+    /// Whether the focused text field has selected text.
+    pub has_selection: bool,
+}
 
 /// Reads Egui output.
 #[allow(clippy::too_many_arguments)]
@@ -22,22 +120,40 @@ pub fn process_output_system(
         &mut EguiRenderOutput,
         &mut EguiOutput,
         &EguiContextSettings,
+        Has<EguiOffscreenContext>,
+        Option<&mut EguiContextClipboard>,
     )>,
     #[cfg(all(feature = "manage_clipboard", not(target_os = "android")))]
     mut egui_clipboard: bevy_ecs::system::ResMut<crate::EguiClipboard>,
     mut event: EventWriter<RequestRedraw>,
+    mut text_cursor_event_writer: EventWriter<EguiTextCursorEvent>,
     mut last_cursor_icon: Local<HashMap<Entity, egui::CursorIcon>>,
+    mut last_text_cursor: Local<HashMap<Entity, (Option<egui::Rect>, bool)>>,
     egui_global_settings: Res<EguiGlobalSettings>,
     window_to_egui_context_map: Res<WindowToEguiContextMap>,
+    egui_cursor_icons: Option<Res<EguiCursorIcons>>,
+    clipboard_filter: Option<Res<EguiClipboardFilter>>,
+    #[cfg(feature = "debug_overlay")] debug_overlay: Option<Res<EguiDebugOverlay>>,
 ) {
     let mut should_request_redraw = false;
 
-    for (entity, mut context, mut full_output, mut render_output, mut egui_output, settings) in
-        context_query.iter_mut()
+    for (
+        entity,
+        mut context,
+        mut full_output,
+        mut render_output,
+        mut egui_output,
+        settings,
+        is_offscreen,
+        mut context_clipboard,
+    ) in context_query.iter_mut()
     {
         let ctx = context.get_mut();
         let Some(full_output) = full_output.0.take() else {
-            bevy_log::error!("bevy_egui pass output has not been prepared (if EguiSettings::run_manually is set to true, make sure to call egui::Context::run or egui::Context::begin_pass and egui::Context::end_pass)");
+            // Contexts that are run manually may skip passes, leaving `EguiRenderOutput` to be set by users.
+            if !settings.run_manually {
+                bevy_log::error!("bevy_egui pass output has not been prepared");
+            }
             continue;
         };
         let egui::FullOutput {
@@ -45,30 +161,66 @@ pub fn process_output_system(
             shapes,
             textures_delta,
             pixels_per_point,
-            viewport_output: _,
+            viewport_output,
         } = full_output;
-        let paint_jobs = ctx.tessellate(shapes, pixels_per_point);
-
-        render_output.paint_jobs = paint_jobs;
+        // Textures are still updated for offscreen contexts, so that they are up to date
+        // if the context starts rendering (e.g. once `EguiOffscreenContext` is removed).
         render_output.textures_delta = textures_delta;
+        // Offscreen contexts don't render anything, so there's no need to tessellate shapes.
+        if !is_offscreen {
+            #[cfg(feature = "debug_overlay")]
+            let shapes = match &debug_overlay {
+                Some(debug_overlay) if debug_overlay.show_clip_rects => {
+                    crate::debug_overlay::with_clip_rect_outlines(shapes)
+                }
+                _ => shapes,
+            };
+            ctx.tessellation_options_mut(|options| *options = settings.tessellation_options);
+            let paint_jobs = ctx.tessellate(shapes, pixels_per_point);
+
+            render_output.paint_jobs = paint_jobs;
+        }
         egui_output.platform_output = platform_output;
+        egui_output.repaint_delay = viewport_output
+            .get(&egui::ViewportId::ROOT)
+            .map(|viewport_output| viewport_output.repaint_delay)
+            .filter(|repaint_delay| *repaint_delay != std::time::Duration::MAX);
 
         for command in &egui_output.platform_output.commands {
             match command {
-                egui::OutputCommand::CopyText(_text) =>
-                {
+                egui::OutputCommand::CopyText(text) => {
+                    let text = match &clipboard_filter {
+                        Some(clipboard_filter) => match clipboard_filter.apply(text) {
+                            Some(text) => std::borrow::Cow::Owned(text),
+                            None => continue,
+                        },
+                        None => std::borrow::Cow::Borrowed(text.as_str()),
+                    };
+                    if let Some(context_clipboard) = context_clipboard.as_deref_mut() {
+                        if !text.is_empty() {
+                            context_clipboard.text = Some(text.into_owned());
+                        }
+                        continue;
+                    }
                     #[cfg(all(feature = "manage_clipboard", not(target_os = "android")))]
-                    if !_text.is_empty() {
-                        egui_clipboard.set_text(_text);
+                    if !text.is_empty() {
+                        egui_clipboard.set_text(&text);
                     }
                 }
                 egui::OutputCommand::CopyImage(_image) => {
+                    // Context clipboards don't support images.
+                    if context_clipboard.is_some() {
+                        continue;
+                    }
                     #[cfg(all(feature = "manage_clipboard", not(target_os = "android")))]
                     egui_clipboard.set_image(_image);
                 }
                 egui::OutputCommand::OpenUrl(_url) => {
                     #[cfg(feature = "open_url")]
                     {
+                        if !settings.open_url_externally {
+                            continue;
+                        }
                         let egui::output::OpenUrl { url, new_tab } = _url;
                         let target = if *new_tab {
                             "_blank"
@@ -90,14 +242,36 @@ pub fn process_output_system(
             }
         }
 
+        let text_cursor = match &egui_output.platform_output.ime {
+            Some(ime) => (Some(ime.cursor_rect), has_text_selection(ctx)),
+            None => (None, false),
+        };
+        let last_text_cursor = last_text_cursor.entry(entity).or_insert((None, false));
+        if *last_text_cursor != text_cursor {
+            *last_text_cursor = text_cursor;
+            text_cursor_event_writer.write(EguiTextCursorEvent {
+                context: entity,
+                cursor_rect: text_cursor.0,
+                has_selection: text_cursor.1,
+            });
+        }
+
         if egui_global_settings.enable_cursor_icon_updates && settings.enable_cursor_icon_updates {
             if let Some(window_entity) = window_to_egui_context_map.context_to_window.get(&entity) {
                 let last_cursor_icon = last_cursor_icon.entry(entity).or_default();
-                if *last_cursor_icon != egui_output.platform_output.cursor_icon {
-                    commands.entity(*window_entity).insert(CursorIcon::System(
-                        helpers::egui_to_winit_cursor_icon(egui_output.platform_output.cursor_icon)
-                            .unwrap_or(bevy_window::SystemCursorIcon::Default),
-                    ));
+                let cursor_icons_changed = egui_cursor_icons
+                    .as_ref()
+                    .is_some_and(|cursor_icons| cursor_icons.is_changed());
+                if *last_cursor_icon != egui_output.platform_output.cursor_icon
+                    || cursor_icons_changed
+                {
+                    let cursor_icon = match &egui_cursor_icons {
+                        Some(cursor_icons) => {
+                            cursor_icons.get(egui_output.platform_output.cursor_icon)
+                        }
+                        None => default_cursor_icon(egui_output.platform_output.cursor_icon),
+                    };
+                    commands.entity(*window_entity).insert(cursor_icon);
                     *last_cursor_icon = egui_output.platform_output.cursor_icon;
                 }
             }
@@ -111,3 +285,132 @@ pub fn process_output_system(
         event.write(RequestRedraw);
     }
 }
+
+/// Returns whether the focused text field of the context has selected text.
+fn has_text_selection(ctx: &egui::Context) -> bool {
+    ctx.memory(|memory| memory.focused())
+        .and_then(|id| egui::TextEdit::load_state(ctx, id))
+        .and_then(|state| state.cursor.char_range())
+        .is_some_and(|range| !range.is_empty())
+}
+
+/// Sets [`bevy_window::Window::ime_position`] to the position of the text cursor of a focused Egui text field,
+/// so that IME candidate popups (e.g. for CJK input) appear next to it.
+///
+/// The position is read from [`egui::output::IMEOutput::cursor_rect`] of contexts rendering to windows.
+pub fn write_ime_position_system(
+    contexts: Query<(
+        Entity,
+        &EguiOutput,
+        &EguiContextSettings,
+        &EguiContextComputedScaleFactor,
+    )>,
+    window_to_egui_context_map: Res<WindowToEguiContextMap>,
+    mut windows: Query<&mut bevy_window::Window>,
+) {
+    for (entity, egui_output, settings, computed_scale_factor) in contexts.iter() {
+        if !settings.ime_enabled {
+            continue;
+        }
+        let Some(ime) = &egui_output.platform_output.ime else {
+            continue;
+        };
+        let Some(mut window) = window_to_egui_context_map
+            .context_to_window
+            .get(&entity)
+            .and_then(|window_entity| windows.get_mut(*window_entity).ok())
+        else {
+            continue;
+        };
+
+        // Egui points are converted into logical window pixels. The popup is placed below the position,
+        // so we use the bottom of the cursor rect to avoid covering the text line.
+        let scale = computed_scale_factor.scale_factor / window.resolution.scale_factor();
+        let ime_position = helpers::egui_pos2_into_vec2(ime.cursor_rect.left_bottom()) * scale;
+        if window.ime_position != ime_position {
+            window.ime_position = ime_position;
+        }
+    }
+}
+
+/// Is sent when an Egui drag-and-drop payload of a registered type is released outside of any Egui area
+/// (e.g. over a 3D scene), see [`EguiDragOutAppExt::add_egui_drag_out_payload`].
+///
+/// The payload itself can be read with [`egui::DragAndDrop::payload`] if you store it elsewhere,
+/// as Egui clears it at the end of the pass in which the pointer is released.
+#[derive(Event, BufferedEvent, Clone, Debug)]
+pub struct EguiDragOutEvent {
+    /// The context in which the payload was dragged.
+    pub context: Entity,
+    /// [`TypeId`] of the payload.
+    pub payload_type_id: TypeId,
+}
+
+type HasPayloadFn = fn(&egui::Context) -> bool;
+
+/// Stores payload types registered with [`EguiDragOutAppExt::add_egui_drag_out_payload`].
+#[derive(Resource, Default)]
+pub struct EguiDragOutPayloadTypes {
+    types: Arc<Mutex<Vec<(TypeId, HasPayloadFn)>>>,
+    pending_events: Arc<Mutex<Vec<EguiDragOutEvent>>>,
+}
+
+/// Adds [`EguiDragOutEvent`] support for payload types.
+pub trait EguiDragOutAppExt {
+    /// Makes `bevy_egui` send [`EguiDragOutEvent`] when a payload of type `T` (set with
+    /// [`egui::Response::dnd_set_drag_payload`] or [`egui::Ui::dnd_drag_source`]) is released outside of Egui areas.
+    fn add_egui_drag_out_payload<T: Any + Send + Sync>(&mut self) -> &mut Self;
+}
+
+impl EguiDragOutAppExt for App {
+    fn add_egui_drag_out_payload<T: Any + Send + Sync>(&mut self) -> &mut Self {
+        let payload_types = self
+            .world_mut()
+            .get_resource_or_init::<EguiDragOutPayloadTypes>();
+        let mut types = payload_types.types.lock().unwrap();
+        let type_id = TypeId::of::<T>();
+        if !types.iter().any(|(id, _)| *id == type_id) {
+            types.push((type_id, egui::DragAndDrop::has_payload_of_type::<T>));
+        }
+        drop(types);
+        self
+    }
+}
+
+/// Installs begin pass callbacks that detect payloads released outside of Egui areas for new contexts.
+pub fn setup_drag_out_callbacks_system(
+    payload_types: Res<EguiDragOutPayloadTypes>,
+    mut contexts: Query<(Entity, &mut EguiContext), Added<EguiContext>>,
+) {
+    for (entity, mut context) in contexts.iter_mut() {
+        let types = payload_types.types.clone();
+        let pending_events = payload_types.pending_events.clone();
+        // Egui clears drag-and-drop payloads at the end of the pass in which the pointer is released,
+        // so we have to check for them at the beginning of the pass.
+        context.get_mut().on_begin_pass(
+            "bevy_egui_drag_out",
+            Arc::new(move |ctx| {
+                if !ctx.input(|input| input.pointer.any_released()) || ctx.is_pointer_over_area() {
+                    return;
+                }
+                for (payload_type_id, has_payload) in types.lock().unwrap().iter() {
+                    if has_payload(ctx) {
+                        pending_events.lock().unwrap().push(EguiDragOutEvent {
+                            context: entity,
+                            payload_type_id: *payload_type_id,
+                        });
+                    }
+                }
+            }),
+        );
+    }
+}
+
+/// Sends [`EguiDragOutEvent`] events detected during Egui passes.
+pub fn write_drag_out_events_system(
+    payload_types: Res<EguiDragOutPayloadTypes>,
+    mut drag_out_event_writer: EventWriter<EguiDragOutEvent>,
+) {
+    let events = std::mem::take(&mut *payload_types.pending_events.lock().unwrap());
+    drag_out_event_writer.write_batch(events);
+}
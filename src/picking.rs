@@ -11,11 +11,13 @@ use bevy_ecs::{
     error::Result,
     observer::Trigger,
     prelude::{AnyOf, Commands, Query, With},
+    system::SystemParam,
 };
 use bevy_math::{Ray3d, Vec2};
 use bevy_picking::{
     events::{Move, Out, Over, Pointer},
     mesh_picking::ray_cast::RayMeshHit,
+    pointer::Location,
     prelude::{MeshRayCast, MeshRayCastSettings, RayCastVisibility},
     Pickable,
 };
@@ -33,115 +35,166 @@ use wgpu_types::PrimitiveTopology;
 #[require(Pickable)]
 pub struct PickableEguiContext(pub Entity);
 
+/// Ray-casts meshes rendering pickable Egui contexts to translate pointer locations into Egui coordinates.
+#[derive(SystemParam)]
+pub struct PickableEguiContextRayCast<'w, 's> {
+    mesh_ray_cast: MeshRayCast<'w, 's>,
+    egui_contexts: Query<'w, 's, (&'static Camera, &'static GlobalTransform), With<EguiContext>>,
+    pickable_egui_context_query: Query<
+        'w,
+        's,
+        (
+            &'static PickableEguiContext,
+            AnyOf<(&'static Mesh2d, &'static Mesh3d)>,
+        ),
+    >,
+    primary_window_query: Query<'w, 's, Entity, With<PrimaryWindow>>,
+    meshes: Res<'w, Assets<Mesh>>,
+}
+
+impl PickableEguiContextRayCast<'_, '_> {
+    /// Returns a picked Egui context and a pointer position in its logical space.
+    ///
+    /// The position is computed by interpolating the UV coordinates of the hit mesh triangle.
+    pub fn pointer_position(
+        &mut self,
+        pointer_location: &Location,
+        camera: Entity,
+    ) -> Result<Option<(Entity, egui::Pos2)>> {
+        let NormalizedRenderTarget::Window(_) = pointer_location.target else {
+            return Ok(None);
+        };
+
+        // Ray-cast attempting to find the context again.
+        // TODO: track https://github.com/bevyengine/bevy/issues/19883 - once it's fixed, we can avoid the double-work with ray-casting again.
+        let Ok((context_camera, global_transform)) = self.egui_contexts.get(camera) else {
+            return Ok(None);
+        };
+        let pickable_egui_context_query = &self.pickable_egui_context_query;
+        let settings = MeshRayCastSettings {
+            visibility: RayCastVisibility::Any,
+            filter: &|entity| pickable_egui_context_query.contains(entity),
+            early_exit_test: &|_| true,
+        };
+        let Some(ray) = make_ray(
+            &self.primary_window_query,
+            context_camera,
+            global_transform,
+            &bevy_picking::pointer::PointerLocation {
+                location: Some(pointer_location.clone()),
+            },
+        ) else {
+            return Ok(None);
+        };
+        let &[(
+            hit_entity,
+            RayMeshHit {
+                triangle_index: Some(triangle_index),
+                barycentric_coords,
+                ..
+            },
+        )] = self.mesh_ray_cast.cast_ray(ray, &settings)
+        else {
+            return Ok(None);
+        };
+
+        // At this point, we expect that the context exists, since we checked that with the ray cast filter.
+        let (&PickableEguiContext(context), mesh) = pickable_egui_context_query.get(hit_entity)?;
+        let (egui_mesh_camera, _) = self.egui_contexts.get(context)?;
+
+        // Read triangle indices and the respective UVs of the mesh.
+        let handle = match mesh {
+            (Some(handle), None) => handle.0.clone(),
+            (None, Some(handle)) => handle.0.clone(),
+            _ => unreachable!(),
+        };
+        let Some(mesh) = self.meshes.get(handle.id()) else {
+            return Ok(None);
+        };
+        // The bevy_picking ray cast backend expects only the TriangleList primitive topology (at least that was the case at the moment of writing).
+        if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+            panic!(
+                "Unexpected primitive topology for a picked mesh ({:?}): {:?}",
+                hit_entity,
+                mesh.primitive_topology()
+            );
+        }
+        let Some(indices) = mesh.indices() else {
+            return Ok(None);
+        };
+        let Some(uv_values) =
+            mesh.attribute(Mesh::ATTRIBUTE_UV_0)
+                .and_then(|values| match (values, indices) {
+                    (VertexAttributeValues::Float32x2(uvs), Indices::U16(indices)) => {
+                        uv_values_for_triangle(indices, triangle_index, uvs)
+                    }
+                    (VertexAttributeValues::Float32x2(uvs), Indices::U32(indices)) => {
+                        uv_values_for_triangle(indices, triangle_index, uvs)
+                    }
+                    _ => None,
+                })
+        else {
+            return Ok(None);
+        };
+
+        // Interpolate UVs based on the barycentric coordinates.
+        let uv = Vec2::from_array(uv_values[0]) * barycentric_coords.x
+            + Vec2::from_array(uv_values[1]) * barycentric_coords.y
+            + Vec2::from_array(uv_values[2]) * barycentric_coords.z;
+
+        let Some(viewport_size) = egui_mesh_camera.logical_target_size() else {
+            return Ok(None);
+        };
+        Ok(Some((
+            context,
+            helpers::vec2_into_egui_pos2(viewport_size * uv),
+        )))
+    }
+}
+
 /// Ray-casts a mesh rendering a pickable Egui context and updates its [`EguiContextPointerPosition`] component.
 pub fn handle_move_system(
     trigger: Trigger<Pointer<Move>>,
-    mut mesh_ray_cast: MeshRayCast,
+    mut ray_cast: PickableEguiContextRayCast,
     mut egui_pointers: Query<&mut EguiContextPointerPosition>,
-    egui_contexts: Query<(&Camera, &GlobalTransform), With<EguiContext>>,
-    pickable_egui_context_query: Query<(&PickableEguiContext, AnyOf<(&Mesh2d, &Mesh3d)>)>,
-    primary_window_query: Query<Entity, With<PrimaryWindow>>,
-    meshes: Res<Assets<Mesh>>,
 ) -> Result {
-    let NormalizedRenderTarget::Window(_) = trigger.pointer_location.target else {
-        return Ok(());
-    };
-
-    // Ray-cast attempting to find the context again.
-    // TODO: track https://github.com/bevyengine/bevy/issues/19883 - once it's fixed, we can avoid the double-work with ray-casting again.
-    let Ok((context_camera, global_transform)) = egui_contexts.get(trigger.hit.camera) else {
-        return Ok(());
-    };
-    let settings = MeshRayCastSettings {
-        visibility: RayCastVisibility::Any,
-        filter: &|entity| pickable_egui_context_query.contains(entity),
-        early_exit_test: &|_| true,
-    };
-    let Some(ray) = make_ray(
-        &primary_window_query,
-        context_camera,
-        global_transform,
-        &bevy_picking::pointer::PointerLocation {
-            location: Some(trigger.pointer_location.clone()),
-        },
-    ) else {
-        return Ok(());
-    };
-    let &[(
-        hit_entity,
-        RayMeshHit {
-            triangle_index: Some(triangle_index),
-            barycentric_coords,
-            ..
-        },
-    )] = mesh_ray_cast.cast_ray(ray, &settings)
+    let Some((context, position)) =
+        ray_cast.pointer_position(&trigger.pointer_location, trigger.hit.camera)?
     else {
         return Ok(());
     };
 
-    // At this point, we expect that the context exists, since we checked that with the ray cast filter.
-    let (&PickableEguiContext(context), mesh) = pickable_egui_context_query.get(hit_entity)?;
-    let (egui_mesh_camera, _) = egui_contexts.get(context)?;
-
-    // Read triangle indices and the respective UVs of the mesh.
-    let handle = match mesh {
-        (Some(handle), None) => handle.0.clone(),
-        (None, Some(handle)) => handle.0.clone(),
-        _ => unreachable!(),
-    };
-    let Some(mesh) = meshes.get(handle.id()) else {
-        return Ok(());
-    };
-    // The bevy_picking ray cast backend expects only the TriangleList primitive topology (at least that was the case at the moment of writing).
-    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
-        panic!(
-            "Unexpected primitive topology for a picked mesh ({:?}): {:?}",
-            trigger.target(),
-            mesh.primitive_topology()
-        );
-    }
-    let Some(indices) = mesh.indices() else {
-        return Ok(());
-    };
-    let Some(uv_values) =
-        mesh.attribute(Mesh::ATTRIBUTE_UV_0)
-            .and_then(|values| match (values, indices) {
-                (VertexAttributeValues::Float32x2(uvs), Indices::U16(indices)) => {
-                    uv_values_for_triangle(indices, triangle_index, uvs)
-                }
-                (VertexAttributeValues::Float32x2(uvs), Indices::U32(indices)) => {
-                    uv_values_for_triangle(indices, triangle_index, uvs)
-                }
-                _ => None,
-            })
-    else {
-        return Ok(());
-    };
-
-    // Interpolate UVs based on the barycentric coordinates.
-    let uv = Vec2::from_array(uv_values[0]) * barycentric_coords.x
-        + Vec2::from_array(uv_values[1]) * barycentric_coords.y
-        + Vec2::from_array(uv_values[2]) * barycentric_coords.z;
-
     // The only thing we need to do here from the Egui context perspective is to update the `EguiContextPointerPosition` component.
     // Other input systems will take care of the rest.
-    let Some(viewport_size) = egui_mesh_camera.logical_target_size() else {
-        return Ok(());
-    };
-    egui_pointers.get_mut(context)?.position = helpers::vec2_into_egui_pos2(viewport_size * uv);
+    egui_pointers.get_mut(context)?.position = position;
 
     Ok(())
 }
 
-/// Inserts the [`HoveredNonWindowEguiContext`] resource containing the hovered Egui context.
+/// Inserts the [`HoveredNonWindowEguiContext`] resource containing the hovered Egui context
+/// and updates its [`EguiContextPointerPosition`] component.
 pub fn handle_over_system(
     trigger: Trigger<Pointer<Over>>,
     pickable_egui_context_query: Query<&PickableEguiContext>,
+    mut ray_cast: PickableEguiContextRayCast,
+    mut egui_pointers: Query<&mut EguiContextPointerPosition>,
     mut commands: Commands,
-) {
-    if let Ok(&PickableEguiContext(context)) = pickable_egui_context_query.get(trigger.target()) {
-        commands.insert_resource(HoveredNonWindowEguiContext(context));
+) -> Result {
+    let Ok(&PickableEguiContext(context)) = pickable_egui_context_query.get(trigger.target())
+    else {
+        return Ok(());
+    };
+    commands.insert_resource(HoveredNonWindowEguiContext(context));
+
+    // Make sure that a click following right after entering the mesh lands at the right spot,
+    // even if no move events have been sent yet.
+    if let Some((hit_context, position)) =
+        ray_cast.pointer_position(&trigger.pointer_location, trigger.hit.camera)?
+    {
+        egui_pointers.get_mut(hit_context)?.position = position;
     }
+
+    Ok(())
 }
 
 /// Removes the [`HoveredNonWindowEguiContext`] resource if it contains the Egui context that the pointer has left.
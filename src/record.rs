@@ -0,0 +1,214 @@
+use crate::{input::EguiInputEvent, EguiContextSettings, EguiInput};
+use bevy_ecs::{
+    entity::Entity,
+    event::{EventReader, EventWriter},
+    resource::Resource,
+    system::{Commands, Query, Res, ResMut},
+};
+use bevy_time::{Real, Time};
+
+/// A sequence of Egui events recorded with [`EguiInputRecorder`], which can be replayed with [`EguiInputReplay`].
+///
+/// With the `serde` feature enabled, the recording can be serialized with a format of your choice
+/// (e.g. to attach it to a bug report).
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EguiInputRecording {
+    /// Recorded frames, one per app update.
+    pub frames: Vec<EguiRecordedFrame>,
+}
+
+/// Egui events received by a context during a single frame.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EguiRecordedFrame {
+    /// Time (in seconds) since the start of the recording.
+    pub time: f64,
+    /// Events, in the order they were received.
+    pub events: Vec<egui::Event>,
+}
+
+/// Records [`EguiInputEvent`] events of a single context while the resource exists.
+///
+/// Insert the resource to start recording, remove it to stop:
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_egui::{record::EguiInputRecorder, PrimaryEguiContext};
+/// fn start_recording_system(
+///     mut commands: Commands,
+///     primary_context: Single<Entity, With<PrimaryEguiContext>>,
+/// ) {
+///     commands.insert_resource(EguiInputRecorder::new(*primary_context));
+/// }
+///
+/// fn stop_recording_system(world: &mut World) {
+///     if let Some(recorder) = world.remove_resource::<EguiInputRecorder>() {
+///         let recording = recorder.into_recording();
+///         info!("Recorded {} frames", recording.frames.len());
+///     }
+/// }
+/// ```
+#[derive(Resource, Clone, Debug)]
+pub struct EguiInputRecorder {
+    context: Entity,
+    start_time: Option<f64>,
+    recording: EguiInputRecording,
+}
+
+impl EguiInputRecorder {
+    /// Creates a recorder for a context.
+    pub fn new(context: Entity) -> Self {
+        Self {
+            context,
+            start_time: None,
+            recording: EguiInputRecording::default(),
+        }
+    }
+
+    /// Returns the recorded context.
+    pub fn context(&self) -> Entity {
+        self.context
+    }
+
+    /// Returns the events recorded so far.
+    pub fn recording(&self) -> &EguiInputRecording {
+        &self.recording
+    }
+
+    /// Consumes the recorder, returning the recorded events.
+    pub fn into_recording(self) -> EguiInputRecording {
+        self.recording
+    }
+}
+
+/// Replays an [`EguiInputRecording`] into a context, one recorded frame per app update.
+///
+/// While the replay is in progress, [`EguiContextSettings::input_enabled`] of the context is set to `false`,
+/// so that real input doesn't interfere with the replayed events, and [`egui::RawInput::time`] is set to the recorded time
+/// (offset by the time the replay started).
+/// Once all the frames are replayed, the setting is restored and the resource is removed.
+/// The setting is also restored if the resource is removed before the replay finishes
+/// (see [`restore_input_after_replay_system`]).
+#[derive(Resource, Clone, Debug)]
+pub struct EguiInputReplay {
+    context: Entity,
+    recording: EguiInputRecording,
+    next_frame: usize,
+    start_time: Option<f64>,
+    input_enabled: Option<bool>,
+}
+
+impl EguiInputReplay {
+    /// Creates a replay of a recording into a context.
+    pub fn new(context: Entity, recording: EguiInputRecording) -> Self {
+        Self {
+            context,
+            recording,
+            next_frame: 0,
+            start_time: None,
+            input_enabled: None,
+        }
+    }
+
+    /// Returns the number of frames that haven't been replayed yet.
+    pub fn remaining_frames(&self) -> usize {
+        self.recording.frames.len() - self.next_frame
+    }
+
+    fn current_frame(&self) -> Option<&EguiRecordedFrame> {
+        self.next_frame
+            .checked_sub(1)
+            .and_then(|index| self.recording.frames.get(index))
+    }
+}
+
+/// Records [`EguiInputEvent`] events if the [`EguiInputRecorder`] resource exists.
+pub fn record_egui_input_system(
+    mut recorder: ResMut<EguiInputRecorder>,
+    mut egui_input_event_reader: EventReader<EguiInputEvent>,
+    time: Res<Time<Real>>,
+) {
+    let elapsed = time.elapsed_secs_f64();
+    let start_time = *recorder.start_time.get_or_insert(elapsed);
+    let context = recorder.context;
+    let events = egui_input_event_reader
+        .read()
+        .filter(|event| event.context == context)
+        .map(|event| event.event.clone())
+        .collect();
+    recorder.recording.frames.push(EguiRecordedFrame {
+        time: elapsed - start_time,
+        events,
+    });
+}
+
+/// Sends [`EguiInputEvent`] events of the next recorded frame if the [`EguiInputReplay`] resource exists.
+pub fn replay_egui_input_system(
+    mut commands: Commands,
+    mut replay: ResMut<EguiInputReplay>,
+    mut contexts: Query<&mut EguiContextSettings>,
+    mut egui_input_event_writer: EventWriter<EguiInputEvent>,
+    time: Res<Time<Real>>,
+) {
+    let Ok(mut settings) = contexts.get_mut(replay.context) else {
+        bevy_log::warn!(
+            "Egui context ({:?}) doesn't exist, stopping the input replay",
+            replay.context
+        );
+        commands.remove_resource::<EguiInputReplay>();
+        return;
+    };
+
+    if replay.input_enabled.is_none() {
+        replay.input_enabled = Some(settings.input_enabled);
+        replay.start_time = Some(time.elapsed_secs_f64());
+        settings.input_enabled = false;
+    }
+
+    let Some(frame) = replay.recording.frames.get(replay.next_frame) else {
+        settings.input_enabled = replay.input_enabled.unwrap_or(true);
+        commands.remove_resource::<EguiInputReplay>();
+        return;
+    };
+    let context = replay.context;
+    egui_input_event_writer.write_batch(
+        frame
+            .events
+            .iter()
+            .cloned()
+            .map(|event| EguiInputEvent { context, event }),
+    );
+    replay.next_frame += 1;
+}
+
+/// Restores [`EguiContextSettings::input_enabled`] if the [`EguiInputReplay`] resource is removed
+/// before the replay finishes.
+pub fn restore_input_after_replay_system(
+    replay: Option<Res<EguiInputReplay>>,
+    mut replayed_context: Local<Option<(Entity, bool)>>,
+    mut contexts: Query<&mut EguiContextSettings>,
+) {
+    if let Some(replay) = replay {
+        if let Some(input_enabled) = replay.input_enabled {
+            *replayed_context = Some((replay.context, input_enabled));
+        }
+        return;
+    }
+    let Some((context, input_enabled)) = replayed_context.take() else {
+        return;
+    };
+    if let Ok(mut settings) = contexts.get_mut(context) {
+        settings.input_enabled = input_enabled;
+    }
+}
+
+/// Overrides [`egui::RawInput::time`] with the time of the replayed frame.
+pub fn write_replay_time_system(replay: Res<EguiInputReplay>, mut contexts: Query<&mut EguiInput>) {
+    let Some(frame) = replay.current_frame() else {
+        return;
+    };
+    if let Ok(mut egui_input) = contexts.get_mut(replay.context) {
+        egui_input.time = Some(replay.start_time.unwrap_or_default() + frame.time);
+    }
+}
@@ -1,6 +1,12 @@
 struct Transform {
     scale: vec2<f32>,
     translation: vec2<f32>,
+    affine: mat3x3<f32>,
+}
+
+struct TextureParams {
+    // Only `x` is used, non-zero if the texture has premultiplied alpha.
+    premultiplied_alpha: vec4<u32>,
 }
 
 struct VertexInput {
@@ -18,6 +24,7 @@ struct VertexOutput {
 @group(0) @binding(0) var<uniform> transform: Transform;
 @group(1) @binding(0) var image_texture: texture_2d<f32>;
 @group(1) @binding(1) var image_sampler: sampler;
+@group(1) @binding(2) var<uniform> texture_params: TextureParams;
 
 // 0-1 linear  from  0-1 sRGB gamma.
 fn linear_from_gamma_rgb(srgb: vec3<f32>) -> vec3<f32> {
@@ -42,7 +49,8 @@ fn gamma_from_linear_rgba(linear_rgba: vec4<f32>) -> vec4<f32> {
 
 @vertex
 fn vs_main(in: VertexInput) -> VertexOutput {
-    let position = in.position * transform.scale + transform.translation;
+    let affine_position = (transform.affine * vec3<f32>(in.position, 1.0)).xy;
+    let position = affine_position * transform.scale + transform.translation;
     // Not sure why Egui does vertex color interpolation in sRGB but here we do it the same way as well.
     return VertexOutput(vec4<f32>(position, 0.0, 1.0), in.color, in.uv);
 }
@@ -50,8 +58,17 @@ fn vs_main(in: VertexInput) -> VertexOutput {
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let texture_color_linear = textureSample(image_texture, image_sampler, in.uv);
-    // We un-premultiply Egui-managed textures on CPU, because Bevy doesn't premultiply it's own images, so here we pre-multiply everything.
-    let texture_color_linear_premultiplied = vec4<f32>(texture_color_linear.rgb * texture_color_linear.a, texture_color_linear.a);
+    // We un-premultiply Egui-managed textures on CPU, because Bevy doesn't premultiply it's own images, so here we pre-multiply everything
+    // (except for user textures that are marked as premultiplied).
+    var texture_color_linear_premultiplied = texture_color_linear;
+    if texture_params.premultiplied_alpha.x == 0u {
+        texture_color_linear_premultiplied = vec4<f32>(texture_color_linear.rgb * texture_color_linear.a, texture_color_linear.a);
+    }
+
+#ifdef LINEAR_COLOR_SPACE
+    // Treat vertex colors as linear and multiply them in the linear space.
+    return texture_color_linear_premultiplied * vec4<f32>(linear_from_gamma_rgb(in.color.rgb), in.color.a);
+#else
     let texture_color_gamma_premultiplied = gamma_from_linear_rgba(texture_color_linear_premultiplied);
 
     // Quoting the Egui's glsl shader:
@@ -59,4 +76,5 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let color_gamma = texture_color_gamma_premultiplied * in.color;
 
     return vec4<f32>(linear_from_gamma_rgb(color_gamma.rgb), color_gamma.a);
+#endif
 }
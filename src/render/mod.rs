@@ -1,4 +1,5 @@
 pub use render_pass::*;
+pub use svg_export::*;
 
 /// Defines Egui node graph.
 pub mod graph {
@@ -13,28 +14,32 @@ pub mod graph {
     pub enum NodeEgui {
         /// Egui rendering pass.
         EguiPass,
+        /// Runs the Egui subgraph for [`crate::EguiImageTarget`] contexts, is added to the root graph.
+        ImageTargets,
     }
 }
 
 use crate::{
     render::graph::{NodeEgui, SubGraphEgui},
-    EguiContextSettings, EguiRenderOutput, RenderComputedScaleFactor,
+    EguiColorSpace, EguiContext, EguiContextComputedScaleFactor, EguiContextMirror,
+    EguiContextSettings, EguiContextTransform, EguiImageTarget, EguiRenderOutput,
+    RenderComputedScaleFactor, RenderEguiContextSettings,
 };
 use bevy_app::SubApp;
-use bevy_asset::{weak_handle, Handle, RenderAssetUsages};
+use bevy_asset::{weak_handle, AssetId, Assets, Handle, RenderAssetUsages};
 use bevy_ecs::{
     component::Component,
     entity::Entity,
-    query::Has,
+    query::{Has, With, Without},
     resource::Resource,
     system::{Commands, Local, ResMut},
-    world::{FromWorld, World},
+    world::{FromWorld, Mut, World},
 };
 use bevy_image::{
     BevyDefault, Image, ImageAddressMode, ImageFilterMode, ImageSampler, ImageSamplerDescriptor,
 };
-use bevy_math::{Mat4, UVec4};
-use bevy_platform::collections::HashSet;
+use bevy_math::{Mat4, UVec2, UVec4};
+use bevy_platform::collections::{HashMap, HashSet};
 use bevy_render::{
     camera::Camera,
     mesh::VertexBufferLayout,
@@ -42,16 +47,17 @@ use bevy_render::{
     render_graph::{Node, NodeRunError, RenderGraph, RenderGraphContext},
     render_phase::TrackedRenderPass,
     render_resource::{
-        binding_types::{sampler, texture_2d, uniform_buffer},
-        BindGroupLayout, BindGroupLayoutEntries, FragmentState, RenderPipelineDescriptor,
-        SpecializedRenderPipeline, VertexState,
+        binding_types::{sampler, texture_2d, uniform_buffer, uniform_buffer_sized},
+        BindGroupLayout, BindGroupLayoutEntries, Buffer, BufferInitDescriptor, BufferUsages,
+        FragmentState, RenderPipelineDescriptor, SpecializedRenderPipeline, VertexState,
     },
     renderer::{RenderContext, RenderDevice},
-    sync_world::{RenderEntity, TemporaryRenderEntity},
+    sync_world::{MainEntity, RenderEntity, TemporaryRenderEntity},
     view::{ExtractedView, Hdr, RetainedViewEntity, ViewTarget},
     MainWorld,
 };
 use egui::{TextureFilter, TextureOptions};
+use std::num::NonZeroU64;
 use systems::{EguiTextureId, EguiTransform};
 use wgpu_types::{
     BlendState, ColorTargetState, ColorWrites, Extent3d, MultisampleState, PrimitiveState,
@@ -60,6 +66,7 @@ use wgpu_types::{
 };
 
 mod render_pass;
+mod svg_export;
 /// Plugin systems for the render app.
 #[cfg(feature = "render")]
 pub mod systems;
@@ -84,6 +91,25 @@ pub struct EguiCameraView(pub Entity);
 #[derive(Component, Debug)]
 pub struct EguiViewTarget(pub Entity);
 
+/// A render-world component that lives on the Egui view and specifies the
+/// main world entity of the Egui context that the view renders.
+///
+/// Matches the main entity of the view, unless the view belongs to a camera with the [`EguiContextMirror`] component.
+#[derive(Component, Debug)]
+pub struct EguiViewContext(pub MainEntity);
+
+/// A render-world component that lives on the Egui view of a [`EguiImageTarget`] context
+/// and specifies the image to render into. Such views don't have [`EguiViewTarget`].
+#[derive(Component, Debug)]
+pub struct EguiImageTargetView {
+    /// The image to render into.
+    pub image: AssetId<Image>,
+    /// Size of the image in pixels.
+    pub size: UVec2,
+    /// Whether the image has the HDR texture format.
+    pub hdr: bool,
+}
+
 /// Adds and returns an Egui subgraph.
 pub fn get_egui_graph(render_app: &mut SubApp) -> RenderGraph {
     let pass_node = EguiPassNode::new(render_app.world_mut());
@@ -116,13 +142,58 @@ impl Node for RunEguiSubgraphOnEguiViewNode {
     }
 }
 
+/// A [`Node`] that executes the Egui rendering subgraph on the views of [`EguiImageTarget`] contexts.
+pub struct RunEguiSubgraphOnImageTargetsNode;
+
+impl Node for RunEguiSubgraphOnImageTargetsNode {
+    fn run<'w>(
+        &self,
+        graph: &mut RenderGraphContext,
+        _: &mut RenderContext<'w>,
+        world: &'w World,
+    ) -> Result<(), NodeRunError> {
+        let Some(mut image_target_views) =
+            world.try_query_filtered::<Entity, With<EguiImageTargetView>>()
+        else {
+            return Ok(());
+        };
+
+        for view_entity in image_target_views.iter(world) {
+            graph.run_sub_graph(SubGraphEgui, vec![], Some(view_entity))?;
+        }
+        Ok(())
+    }
+}
+
 /// Extracts all Egui contexts associated with a camera into the render world.
+///
+/// Cameras with the [`EguiContextMirror`] component get a copy of the render output of the mirrored context.
 pub fn extract_egui_camera_view_system(
     mut commands: Commands,
     mut world: ResMut<MainWorld>,
     mut live_entities: Local<HashSet<RetainedViewEntity>>,
 ) {
     live_entities.clear();
+
+    let mut mirrors_query = world.query_filtered::<(
+        Entity,
+        RenderEntity,
+        &Camera,
+        Has<Hdr>,
+        &EguiContextMirror,
+    ), Without<EguiContext>>();
+    let mirrors = mirrors_query
+        .iter(&world)
+        .map(|(main_entity, render_entity, camera, hdr, mirror)| {
+            (main_entity, render_entity, camera.clone(), hdr, mirror.0)
+        })
+        .collect::<Vec<_>>();
+    let mirror_sources = mirrors
+        .iter()
+        .map(|(.., source)| *source)
+        .collect::<HashSet<_>>();
+    let mut mirrored_contexts = HashMap::<Entity, ExtractedEguiContext>::default();
+
     let mut q = world.query::<(
         Entity,
         RenderEntity,
@@ -130,77 +201,310 @@ pub fn extract_egui_camera_view_system(
         Has<Hdr>,
         &mut EguiRenderOutput,
         &EguiContextSettings,
+        &EguiContextComputedScaleFactor,
+        Option<&EguiContextTransform>,
     )>();
 
-    for (main_entity, render_entity, camera, hdr, mut egui_render_output, settings) in
-        &mut q.iter_mut(&mut world)
+    for (
+        main_entity,
+        render_entity,
+        camera,
+        hdr,
+        mut egui_render_output,
+        settings,
+        computed_scale_factor,
+        transform,
+    ) in &mut q.iter_mut(&mut world)
     {
         // Move Egui shapes and textures out of the main world into the render one.
         let egui_render_output = std::mem::take(egui_render_output.as_mut());
 
-        // Ignore inactive cameras.
-        if !camera.is_active {
+        let context = ExtractedEguiContext {
+            render_output: egui_render_output,
+            scale_factor: computed_scale_factor.scale_factor,
+            settings: RenderEguiContextSettings {
+                color_space: settings.color_space,
+                render_background: settings.render_background,
+                flip_y: settings.flip_y,
+                transform: transform.copied().unwrap_or_default().0,
+            },
+            skip_empty_render_output: settings.skip_empty_render_output,
+        };
+        if mirror_sources.contains(&main_entity) {
+            let mirrored_context = ExtractedEguiContext {
+                // Textures delta is processed in the main world, so mirrors need only shapes.
+                render_output: EguiRenderOutput {
+                    paint_jobs: context.render_output.paint_jobs.clone(),
+                    textures_delta: Default::default(),
+                },
+                settings: context.settings.clone(),
+                ..context
+            };
+            mirrored_contexts.insert(main_entity, mirrored_context);
+        }
+
+        extract_egui_view(
+            &mut commands,
+            &mut live_entities,
+            main_entity,
+            render_entity,
+            camera,
+            hdr,
+            main_entity,
+            context,
+        );
+    }
+
+    for (main_entity, render_entity, camera, hdr, source) in mirrors {
+        let Some(context) = mirrored_contexts.get(&source).cloned() else {
+            // The mirrored context doesn't exist (or isn't rendered).
             commands
                 .get_entity(render_entity)
                 .expect("Camera entity wasn't synced.")
                 .remove::<EguiCameraView>();
             continue;
+        };
+        extract_egui_view(
+            &mut commands,
+            &mut live_entities,
+            main_entity,
+            render_entity,
+            &camera,
+            hdr,
+            source,
+            context,
+        );
+    }
+}
+
+const UI_CAMERA_FAR: f32 = 1000.0;
+const EGUI_CAMERA_SUBVIEW: u32 = 2095931312;
+const UI_CAMERA_TRANSFORM_OFFSET: f32 = -0.1;
+
+#[derive(Clone)]
+struct ExtractedEguiContext {
+    render_output: EguiRenderOutput,
+    scale_factor: f32,
+    settings: RenderEguiContextSettings,
+    skip_empty_render_output: bool,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn extract_egui_view(
+    commands: &mut Commands,
+    live_entities: &mut HashSet<RetainedViewEntity>,
+    main_entity: Entity,
+    render_entity: Entity,
+    camera: &Camera,
+    hdr: bool,
+    context_entity: Entity,
+    context: ExtractedEguiContext,
+) {
+    // Ignore inactive cameras and (if enabled in the settings) contexts that have nothing to paint.
+    // Textures delta is processed in the main world, so it's fine to drop it here.
+    if !camera.is_active
+        || (context.skip_empty_render_output && context.render_output.paint_jobs.is_empty())
+    {
+        commands
+            .get_entity(render_entity)
+            .expect("Camera entity wasn't synced.")
+            .remove::<EguiCameraView>();
+        return;
+    }
+
+    if let Some(physical_viewport_rect) = camera.physical_viewport_rect() {
+        // Use a projection matrix with the origin in the top left instead of the bottom left that comes with OrthographicProjection.
+        let projection_matrix = Mat4::orthographic_rh(
+            0.0,
+            physical_viewport_rect.width() as f32,
+            physical_viewport_rect.height() as f32,
+            0.0,
+            0.0,
+            UI_CAMERA_FAR,
+        );
+        // We use `EGUI_CAMERA_SUBVIEW` here so as not to conflict with the
+        // main 3D or 2D camera or UI view, which will have subview index 0 or 1.
+        let retained_view_entity =
+            RetainedViewEntity::new(main_entity.into(), None, EGUI_CAMERA_SUBVIEW);
+        // Creates the UI view.
+        let ui_camera_view = commands
+            .spawn((
+                ExtractedView {
+                    retained_view_entity,
+                    clip_from_view: projection_matrix,
+                    world_from_view: bevy_transform::components::GlobalTransform::from_xyz(
+                        0.0,
+                        0.0,
+                        UI_CAMERA_FAR + UI_CAMERA_TRANSFORM_OFFSET,
+                    ),
+                    clip_from_world: None,
+                    hdr,
+                    viewport: UVec4::from((
+                        physical_viewport_rect.min,
+                        physical_viewport_rect.size(),
+                    )),
+                    color_grading: Default::default(),
+                },
+                // Link to the main camera view.
+                EguiViewTarget(render_entity),
+                EguiViewContext(context_entity.into()),
+                context.render_output,
+                RenderComputedScaleFactor {
+                    scale_factor: context.scale_factor,
+                },
+                context.settings,
+                TemporaryRenderEntity,
+            ))
+            .id();
+
+        let mut entity_commands = commands
+            .get_entity(render_entity)
+            .expect("Camera entity wasn't synced.");
+        // Link from the main 2D/3D camera view to the UI view.
+        entity_commands.insert(EguiCameraView(ui_camera_view));
+        live_entities.insert(retained_view_entity);
+    }
+}
+
+/// Extracts [`EguiImageTarget`] contexts that are requested to render into the render world.
+pub fn extract_egui_image_target_views_system(
+    mut commands: Commands,
+    mut world: ResMut<MainWorld>,
+) {
+    world.resource_scope(|world, images: Mut<Assets<Image>>| {
+        let mut q = world.query::<(
+            Entity,
+            &mut EguiImageTarget,
+            &mut EguiRenderOutput,
+            &EguiContextSettings,
+            &EguiContextComputedScaleFactor,
+            Option<&EguiContextTransform>,
+        )>();
+
+        for (
+            main_entity,
+            mut image_target,
+            mut egui_render_output,
+            settings,
+            computed_scale_factor,
+            transform,
+        ) in q.iter_mut(world)
+        {
+            if !image_target.continuous && !image_target.render_requested {
+                continue;
+            }
+            let Some(image) = images.get(&image_target.image) else {
+                continue;
+            };
+            let hdr = match image.texture_descriptor.format {
+                ViewTarget::TEXTURE_FORMAT_HDR => true,
+                format if format == TextureFormat::bevy_default() => false,
+                format => {
+                    bevy_log::warn_once!(
+                        "Egui image target {main_entity} has an unsupported texture format: {format:?}"
+                    );
+                    continue;
+                }
+            };
+            let size = image.size();
+            if size.x < 1 || size.y < 1 {
+                continue;
+            }
+            if image_target.render_requested {
+                image_target.render_requested = false;
+            }
+
+            // Move Egui shapes and textures out of the main world into the render one.
+            let render_output = std::mem::take(egui_render_output.as_mut());
+            if settings.skip_empty_render_output && render_output.paint_jobs.is_empty() {
+                continue;
+            }
+
+            commands.spawn((
+                ExtractedView {
+                    retained_view_entity: RetainedViewEntity::new(
+                        main_entity.into(),
+                        None,
+                        EGUI_CAMERA_SUBVIEW,
+                    ),
+                    clip_from_view: Mat4::orthographic_rh(
+                        0.0,
+                        size.x as f32,
+                        size.y as f32,
+                        0.0,
+                        0.0,
+                        UI_CAMERA_FAR,
+                    ),
+                    world_from_view: bevy_transform::components::GlobalTransform::from_xyz(
+                        0.0,
+                        0.0,
+                        UI_CAMERA_FAR + UI_CAMERA_TRANSFORM_OFFSET,
+                    ),
+                    clip_from_world: None,
+                    hdr,
+                    viewport: UVec4::from((UVec2::ZERO, size)),
+                    color_grading: Default::default(),
+                },
+                EguiImageTargetView {
+                    image: image_target.image.id(),
+                    size,
+                    hdr,
+                },
+                EguiViewContext(main_entity.into()),
+                render_output,
+                RenderComputedScaleFactor {
+                    scale_factor: computed_scale_factor.scale_factor,
+                },
+                RenderEguiContextSettings {
+                    color_space: settings.color_space,
+                    render_background: settings.render_background,
+                    flip_y: settings.flip_y,
+                    transform: transform.copied().unwrap_or_default().0,
+                },
+                TemporaryRenderEntity,
+            ));
         }
+    });
+}
 
-        const UI_CAMERA_FAR: f32 = 1000.0;
-        const EGUI_CAMERA_SUBVIEW: u32 = 2095931312;
-        const UI_CAMERA_TRANSFORM_OFFSET: f32 = -0.1;
-
-        if let Some(physical_viewport_rect) = camera.physical_viewport_rect() {
-            // Use a projection matrix with the origin in the top left instead of the bottom left that comes with OrthographicProjection.
-            let projection_matrix = Mat4::orthographic_rh(
-                0.0,
-                physical_viewport_rect.width() as f32,
-                physical_viewport_rect.height() as f32,
-                0.0,
-                0.0,
-                UI_CAMERA_FAR,
-            );
-            // We use `EGUI_CAMERA_SUBVIEW` here so as not to conflict with the
-            // main 3D or 2D camera or UI view, which will have subview index 0 or 1.
-            let retained_view_entity =
-                RetainedViewEntity::new(main_entity.into(), None, EGUI_CAMERA_SUBVIEW);
-            // Creates the UI view.
-            let ui_camera_view = commands
-                .spawn((
-                    ExtractedView {
-                        retained_view_entity,
-                        clip_from_view: projection_matrix,
-                        world_from_view: bevy_transform::components::GlobalTransform::from_xyz(
-                            0.0,
-                            0.0,
-                            UI_CAMERA_FAR + UI_CAMERA_TRANSFORM_OFFSET,
-                        ),
-                        clip_from_world: None,
-                        hdr,
-                        viewport: UVec4::from((
-                            physical_viewport_rect.min,
-                            physical_viewport_rect.size(),
-                        )),
-                        color_grading: Default::default(),
-                    },
-                    // Link to the main camera view.
-                    EguiViewTarget(render_entity),
-                    egui_render_output,
-                    RenderComputedScaleFactor {
-                        scale_factor: settings.scale_factor
-                            * camera.target_scaling_factor().unwrap_or(1.0),
-                    },
-                    TemporaryRenderEntity,
-                ))
-                .id();
+/// Returns `true` if [`premultiply_image_alpha`] supports the texture format.
+pub(crate) fn can_premultiply_image_alpha(format: TextureFormat) -> bool {
+    matches!(
+        format,
+        TextureFormat::Rgba8Unorm
+            | TextureFormat::Bgra8Unorm
+            | TextureFormat::Rgba8UnormSrgb
+            | TextureFormat::Bgra8UnormSrgb
+    )
+}
 
-            let mut entity_commands = commands
-                .get_entity(render_entity)
-                .expect("Camera entity wasn't synced.");
-            // Link from the main 2D/3D camera view to the UI view.
-            entity_commands.insert(EguiCameraView(ui_camera_view));
-            live_entities.insert(retained_view_entity);
+/// Premultiplies the colors of an image by its alpha in place,
+/// see [`crate::EguiUserTextureAlpha::PremultiplyOnUpload`].
+///
+/// Images with formats not supported by [`can_premultiply_image_alpha`] or without data are left intact.
+pub(crate) fn premultiply_image_alpha(image: &mut Image) {
+    if !can_premultiply_image_alpha(image.texture_descriptor.format) {
+        return;
+    }
+    let srgb = image.texture_descriptor.format.is_srgb();
+    let Some(data) = image.data.as_mut() else {
+        return;
+    };
+    for pixel in data.chunks_exact_mut(4) {
+        let alpha = pixel[3];
+        if alpha == u8::MAX {
+            continue;
+        }
+        // All the supported formats store alpha in the last channel.
+        let alpha_f32 = alpha as f32 / 255.0;
+        for channel in &mut pixel[..3] {
+            *channel = if srgb {
+                // Premultiply in the linear space, as that's where the texture is sampled.
+                let linear = egui::ecolor::linear_f32_from_gamma_u8(*channel);
+                egui::ecolor::gamma_u8_from_linear_f32(linear * alpha_f32)
+            } else {
+                ((*channel as u16 * alpha as u16 + 127) / 255) as u8
+            };
         }
     }
 }
@@ -215,6 +519,11 @@ pub struct EguiPipeline {
     pub transform_bind_group_layout: BindGroupLayout,
     /// Texture bind group layout.
     pub texture_bind_group_layout: BindGroupLayout,
+    /// Texture params uniform for textures with straight alpha.
+    pub straight_alpha_texture_params: Buffer,
+    /// Texture params uniform for textures with premultiplied alpha,
+    /// see [`crate::EguiUserTextureAlpha`].
+    pub premultiplied_alpha_texture_params: Buffer,
 }
 
 impl FromWorld for EguiPipeline {
@@ -236,28 +545,62 @@ impl FromWorld for EguiPipeline {
                 (
                     texture_2d(TextureSampleType::Float { filterable: true }),
                     sampler(SamplerBindingType::Filtering),
+                    uniform_buffer_sized(false, NonZeroU64::new(16)),
                 ),
             ),
         );
 
+        // Matches `TextureParams` in the shader, only the first component is used.
+        let create_texture_params = |label, premultiplied_alpha: u32| {
+            render_device.create_buffer_with_data(&BufferInitDescriptor {
+                label: Some(label),
+                contents: bytemuck::cast_slice(&[premultiplied_alpha, 0, 0, 0]),
+                usage: BufferUsages::UNIFORM,
+            })
+        };
+        let straight_alpha_texture_params =
+            create_texture_params("egui_straight_alpha_texture_params", 0);
+        let premultiplied_alpha_texture_params =
+            create_texture_params("egui_premultiplied_alpha_texture_params", 1);
+
         EguiPipeline {
             transform_bind_group_layout,
             texture_bind_group_layout,
+            straight_alpha_texture_params,
+            premultiplied_alpha_texture_params,
         }
     }
 }
 
 /// Key for specialized pipeline.
-#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
 pub struct EguiPipelineKey {
     /// Reflects the value of [`Camera::hdr`].
     pub hdr: bool,
+    /// Reflects the value of [`EguiContextSettings::color_space`].
+    pub color_space: EguiColorSpace,
+}
+
+impl EguiPipelineKey {
+    /// Returns all the possible keys, is used for [`crate::EguiPlugin::prewarm_pipelines`].
+    pub fn all() -> impl Iterator<Item = Self> {
+        [false, true].into_iter().flat_map(|hdr| {
+            [EguiColorSpace::Srgb, EguiColorSpace::Linear]
+                .into_iter()
+                .map(move |color_space| Self { hdr, color_space })
+        })
+    }
 }
 
 impl SpecializedRenderPipeline for EguiPipeline {
     type Key = EguiPipelineKey;
 
     fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let mut shader_defs = Vec::new();
+        if key.color_space == EguiColorSpace::Linear {
+            shader_defs.push("LINEAR_COLOR_SPACE".into());
+        }
+
         RenderPipelineDescriptor {
             label: Some("egui_pipeline".into()),
             layout: vec![
@@ -279,7 +622,7 @@ impl SpecializedRenderPipeline for EguiPipeline {
             },
             fragment: Some(FragmentState {
                 shader: EGUI_SHADER_HANDLE,
-                shader_defs: Vec::new(),
+                shader_defs,
                 entry_point: "fs_main".into(),
                 targets: vec![Some(ColorTargetState {
                     format: if key.hdr {
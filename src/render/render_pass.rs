@@ -1,25 +1,37 @@
-use crate::render::{
-    systems::{EguiPipelines, EguiRenderData, EguiTextureBindGroups, EguiTransforms},
-    DrawPrimitive, EguiViewTarget,
+use crate::{
+    render::{
+        systems::{EguiPipelines, EguiRenderData, EguiTextureBindGroups, EguiTransforms},
+        DrawPrimitive, EguiImageTargetView, EguiViewTarget,
+    },
+    RenderEguiContextSettings,
 };
 use bevy_ecs::{
     query::QueryState,
     world::{Mut, World},
 };
-use bevy_math::{URect, UVec2};
+use bevy_math::{Mat3, URect, UVec2, Vec2};
 use bevy_render::{
     camera::{ExtractedCamera, Viewport},
+    render_asset::RenderAssets,
     render_graph::{Node, NodeRunError, RenderGraphContext},
-    render_resource::{PipelineCache, RenderPassDescriptor},
+    render_resource::{
+        LoadOp, Operations, PipelineCache, RenderPassColorAttachment, RenderPassDescriptor, StoreOp,
+    },
     renderer::RenderContext,
     sync_world::RenderEntity,
+    texture::GpuImage,
     view::{ExtractedView, ViewTarget},
 };
 use wgpu_types::IndexFormat;
 
 /// Egui pass node.
 pub struct EguiPassNode {
-    egui_view_query: QueryState<(&'static ExtractedView, &'static EguiViewTarget)>,
+    egui_view_query: QueryState<(
+        &'static ExtractedView,
+        Option<&'static EguiViewTarget>,
+        Option<&'static EguiImageTargetView>,
+        &'static RenderEguiContextSettings,
+    )>,
     egui_view_target_query: QueryState<(&'static ViewTarget, &'static ExtractedCamera)>,
 }
 
@@ -75,12 +87,8 @@ impl Node for EguiPassNode {
         let input_view_entity = graph.view_entity();
 
         // Query the UI view components.
-        let Ok((view, view_target)) = self.egui_view_query.get_manual(world, input_view_entity)
-        else {
-            return Ok(());
-        };
-
-        let Ok((target, camera)) = self.egui_view_target_query.get_manual(world, view_target.0)
+        let Ok((view, view_target, image_target_view, settings)) =
+            self.egui_view_query.get_manual(world, input_view_entity)
         else {
             return Ok(());
         };
@@ -90,25 +98,69 @@ impl Node for EguiPassNode {
             return Ok(());
         };
 
+        // Egui views either belong to a camera, or render directly into an image (see `EguiImageTarget`).
+        // Only image target views own the whole image and can clear it, contexts of cameras rendering
+        // to images get the background painted as a quad instead (see `prepare_egui_render_target_data_system`).
+        let (mut color_attachment, target_size, clears_background) =
+            match (view_target, image_target_view) {
+                (Some(view_target), _) => {
+                    let Ok((target, camera)) =
+                        self.egui_view_target_query.get_manual(world, view_target.0)
+                    else {
+                        return Ok(());
+                    };
+                    (
+                        target.get_unsampled_color_attachment(),
+                        camera.physical_target_size.unwrap(),
+                        false,
+                    )
+                }
+                (None, Some(image_target_view)) => {
+                    let Some(gpu_image) = world
+                        .resource::<RenderAssets<GpuImage>>()
+                        .get(image_target_view.image)
+                    else {
+                        return Ok(());
+                    };
+                    let color_attachment = RenderPassColorAttachment {
+                        view: &gpu_image.texture_view,
+                        resolve_target: None,
+                        ops: Operations {
+                            load: LoadOp::Load,
+                            store: StoreOp::Store,
+                        },
+                    };
+                    (color_attachment, image_target_view.size, true)
+                }
+                (None, None) => return Ok(()),
+            };
+        if let (Some(background), true) = (settings.render_background, clears_background) {
+            let [r, g, b, a] = egui::Rgba::from(background).to_array();
+            color_attachment.ops.load = LoadOp::Clear(wgpu_types::Color {
+                r: r as f64,
+                g: g as f64,
+                b: b as f64,
+                a: a as f64,
+            });
+        }
+
         let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
             label: Some("egui_pass"),
-            color_attachments: &[Some(target.get_unsampled_color_attachment())],
+            color_attachments: &[Some(color_attachment)],
             depth_stencil_attachment: None,
             timestamp_writes: None,
             occlusion_query_set: None,
         });
-        let Some(viewport) = camera.viewport.clone().or_else(|| {
-            camera.physical_viewport_size.map(|size| Viewport {
-                physical_position: UVec2::ZERO,
-                physical_size: size,
-                ..Default::default()
-            })
-        }) else {
+        // The Egui view always stores the camera's `physical_viewport_rect`, we use it to confine
+        // the pass to the camera viewport (cameras can share a render target, e.g. in split screen).
+        let viewport_rect =
+            URect::from_corners(view.viewport.xy(), view.viewport.xy() + view.viewport.zw());
+        if viewport_rect.is_empty() {
             return Ok(());
-        };
+        }
         render_pass.set_camera_viewport(&Viewport {
             physical_position: UVec2::ZERO,
-            physical_size: camera.physical_target_size.unwrap(),
+            physical_size: target_size,
             ..Default::default()
         });
 
@@ -150,30 +202,37 @@ impl Node for EguiPassNode {
                 );
                 render_pass.set_camera_viewport(&Viewport {
                     physical_position: UVec2::ZERO,
-                    physical_size: camera.physical_target_size.unwrap(),
+                    physical_size: target_size,
                     ..Default::default()
                 });
                 requires_reset = false;
             }
 
+            let clip_rect = transformed_clip_rect(draw_command.clip_rect, settings.transform);
             let clip_urect = URect {
                 min: UVec2 {
-                    x: (draw_command.clip_rect.min.x * data.pixels_per_point).round() as u32,
-                    y: (draw_command.clip_rect.min.y * data.pixels_per_point).round() as u32,
+                    x: (clip_rect.min.x * data.pixels_per_point).round() as u32,
+                    y: (clip_rect.min.y * data.pixels_per_point).round() as u32,
                 },
                 max: UVec2 {
-                    x: (draw_command.clip_rect.max.x * data.pixels_per_point).round() as u32,
-                    y: (draw_command.clip_rect.max.y * data.pixels_per_point).round() as u32,
+                    x: (clip_rect.max.x * data.pixels_per_point).round() as u32,
+                    y: (clip_rect.max.y * data.pixels_per_point).round() as u32,
                 },
             };
 
-            let scissor_rect = clip_urect.intersect(URect {
-                min: viewport.physical_position,
-                max: viewport.physical_position + viewport.physical_size,
-            });
+            let mut scissor_rect = clip_urect.intersect(viewport_rect);
             if scissor_rect.is_empty() {
                 continue;
             }
+            if settings.flip_y {
+                let target_height = target_size.y;
+                scissor_rect = URect::new(
+                    scissor_rect.min.x,
+                    target_height.saturating_sub(scissor_rect.max.y),
+                    scissor_rect.max.x,
+                    target_height.saturating_sub(scissor_rect.min.y),
+                );
+            }
 
             if Some(scissor_rect) != last_scissor_rect {
                 last_scissor_rect = Some(scissor_rect);
@@ -218,7 +277,7 @@ impl Node for EguiPassNode {
                         viewport: command.rect,
                         clip_rect: draw_command.clip_rect,
                         pixels_per_point: data.pixels_per_point,
-                        screen_size_px: [viewport.physical_size.x, viewport.physical_size.y],
+                        screen_size_px: viewport_rect.size().to_array(),
                     };
 
                     let viewport = info.viewport_in_pixels();
@@ -248,3 +307,21 @@ impl Node for EguiPassNode {
         Ok(())
     }
 }
+
+/// Returns the bounding box of the clip rectangle transformed with [`crate::EguiContextTransform`].
+fn transformed_clip_rect(clip_rect: egui::Rect, transform: Mat3) -> egui::Rect {
+    if transform == Mat3::IDENTITY {
+        return clip_rect;
+    }
+    let corners = [
+        clip_rect.left_top(),
+        clip_rect.right_top(),
+        clip_rect.left_bottom(),
+        clip_rect.right_bottom(),
+    ]
+    .map(|corner| {
+        let corner = transform.transform_point2(Vec2::new(corner.x, corner.y));
+        egui::pos2(corner.x.max(0.0), corner.y.max(0.0))
+    });
+    egui::Rect::from_points(&corners)
+}
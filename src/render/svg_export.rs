@@ -0,0 +1,238 @@
+use egui::epaint::{ClippedPrimitive, Primitive, Vertex, WHITE_UV};
+use std::fmt::Write;
+
+/// Converts tessellated Egui shapes (e.g. [`crate::EguiRenderOutput::paint_jobs`]) into an SVG document,
+/// which can be used for implementing "export as vector" features.
+///
+/// Coordinates are in logical points, the SVG size is the union of the (clipped) bounds of the exported meshes.
+/// Each mesh is written as filled triangle paths (strokes are tessellated into triangles as well),
+/// clip rects are written as `clipPath` elements.
+///
+/// Textured triangles (text glyphs and images) are skipped unless `include_textured` is `true`,
+/// in which case they are approximated with their vertex colors (e.g. glyphs become filled quads).
+/// Paint callbacks are always skipped.
+///
+/// Egui feathers shape edges for anti-aliasing, which produces thin semi-transparent triangles.
+/// For cleaner output, disable [`egui::epaint::TessellationOptions::feathering`]
+/// (see [`crate::EguiContextSettings::tessellation_options`]) for the pass you export.
+pub fn paint_jobs_to_svg(paint_jobs: &[ClippedPrimitive], include_textured: bool) -> String {
+    let mut clip_paths = String::new();
+    let mut body = String::new();
+    let mut bounds = egui::Rect::NOTHING;
+    let mut clip_rects = Vec::<egui::Rect>::new();
+
+    for ClippedPrimitive {
+        clip_rect,
+        primitive,
+    } in paint_jobs
+    {
+        let Primitive::Mesh(mesh) = primitive else {
+            continue;
+        };
+        let is_font_texture = mesh.texture_id == egui::TextureId::default();
+        if !is_font_texture && !include_textured {
+            continue;
+        }
+
+        let mut path = SvgPath::default();
+        for triangle in mesh.indices.chunks_exact(3) {
+            let [a, b, c] =
+                [triangle[0], triangle[1], triangle[2]].map(|i| mesh.vertices[i as usize]);
+            let is_textured = !is_font_texture || [a, b, c].iter().any(|v| v.uv != WHITE_UV);
+            if is_textured && !include_textured {
+                continue;
+            }
+            let color = triangle_color(&[a, b, c]);
+            if color.a() == 0 {
+                continue;
+            }
+            path.push_triangle([a.pos, b.pos, c.pos], color);
+        }
+        if path.is_empty() {
+            continue;
+        }
+        // Clip rects may be infinite (e.g. `egui::Rect::EVERYTHING`), so they're limited to the mesh bounds.
+        let clip_rect = &clip_rect.intersect(mesh.calc_bounds());
+
+        let clip_index = match clip_rects.iter().position(|rect| rect == clip_rect) {
+            Some(index) => index,
+            None => {
+                clip_rects.push(*clip_rect);
+                let _ = write!(
+                    clip_paths,
+                    r#"<clipPath id="clip{}"><rect x="{}" y="{}" width="{}" height="{}"/></clipPath>"#,
+                    clip_rects.len() - 1,
+                    clip_rect.min.x,
+                    clip_rect.min.y,
+                    clip_rect.width(),
+                    clip_rect.height(),
+                );
+                clip_rects.len() - 1
+            }
+        };
+        bounds = bounds.union(*clip_rect);
+        let _ = write!(
+            body,
+            r#"<g clip-path="url(#clip{clip_index})">{}</g>"#,
+            path.finish()
+        );
+    }
+
+    if !bounds.is_positive() {
+        bounds = egui::Rect::ZERO;
+    }
+    format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="{} {} {} {}"><defs>{clip_paths}</defs>{body}</svg>"#,
+        bounds.width(),
+        bounds.height(),
+        bounds.min.x,
+        bounds.min.y,
+        bounds.width(),
+        bounds.height(),
+    )
+}
+
+/// Averages the (premultiplied) vertex colors of a triangle.
+fn triangle_color(vertices: &[Vertex; 3]) -> egui::Color32 {
+    let [r, g, b, a] = vertices.iter().fold([0u32; 4], |sum, vertex| {
+        let [r, g, b, a] = vertex.color.to_array();
+        [
+            sum[0] + r as u32,
+            sum[1] + g as u32,
+            sum[2] + b as u32,
+            sum[3] + a as u32,
+        ]
+    });
+    egui::Color32::from_rgba_premultiplied(
+        (r / 3) as u8,
+        (g / 3) as u8,
+        (b / 3) as u8,
+        (a / 3) as u8,
+    )
+}
+
+/// Joins consecutive triangles of the same color into a single `path` element.
+#[derive(Default)]
+struct SvgPath {
+    color: Option<egui::Color32>,
+    data: String,
+    elements: String,
+}
+
+impl SvgPath {
+    fn is_empty(&self) -> bool {
+        self.data.is_empty() && self.elements.is_empty()
+    }
+
+    fn push_triangle(&mut self, [a, b, c]: [egui::Pos2; 3], color: egui::Color32) {
+        if self.color != Some(color) {
+            self.flush();
+            self.color = Some(color);
+        }
+        let _ = write!(
+            self.data,
+            "M{} {}L{} {}L{} {}Z",
+            a.x, a.y, b.x, b.y, c.x, c.y
+        );
+    }
+
+    fn flush(&mut self) {
+        let Some(color) = self.color else {
+            return;
+        };
+        if self.data.is_empty() {
+            return;
+        }
+        let [r, g, b, a] = color.to_srgba_unmultiplied();
+        let _ = write!(
+            self.elements,
+            r#"<path d="{}" fill="rgb({r},{g},{b})""#,
+            self.data
+        );
+        if a < 255 {
+            let _ = write!(self.elements, r#" fill-opacity="{:.3}""#, a as f32 / 255.0);
+        }
+        self.elements.push_str("/>");
+        self.data.clear();
+    }
+
+    fn finish(mut self) -> String {
+        self.flush();
+        self.elements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::paint_jobs_to_svg;
+    use egui::{epaint::ClippedPrimitive, pos2, Color32, Mesh, Rect, TextureId};
+
+    fn colored_rect_job(rect: Rect, clip_rect: Rect, color: Color32) -> ClippedPrimitive {
+        let mut mesh = Mesh::default();
+        mesh.add_colored_rect(rect, color);
+        ClippedPrimitive {
+            clip_rect,
+            primitive: egui::epaint::Primitive::Mesh(mesh),
+        }
+    }
+
+    #[test]
+    fn test_paint_jobs_to_svg_dedups_clip_rects() {
+        let clip_rect = Rect::from_min_max(pos2(10.0, 10.0), pos2(50.0, 50.0));
+        let other_clip_rect = Rect::from_min_max(pos2(0.0, 0.0), pos2(20.0, 20.0));
+        let rect = Rect::from_min_max(pos2(0.0, 0.0), pos2(100.0, 100.0));
+        let svg = paint_jobs_to_svg(
+            &[
+                colored_rect_job(rect, clip_rect, Color32::RED),
+                colored_rect_job(rect, clip_rect, Color32::GREEN),
+                colored_rect_job(rect, other_clip_rect, Color32::BLUE),
+            ],
+            false,
+        );
+
+        assert_eq!(svg.matches("<clipPath").count(), 2);
+        assert_eq!(svg.matches(r#"clip-path="url(#clip0)""#).count(), 2);
+        assert_eq!(svg.matches(r#"clip-path="url(#clip1)""#).count(), 1);
+        assert_eq!(svg.matches("<path").count(), 3);
+        assert!(svg.contains(r#"fill="rgb(255,0,0)""#));
+        assert!(svg.contains(r#"viewBox="0 0 50 50""#));
+    }
+
+    #[test]
+    fn test_paint_jobs_to_svg_filters_textured_triangles() {
+        let clip_rect = Rect::EVERYTHING;
+        let rect = Rect::from_min_max(pos2(0.0, 0.0), pos2(10.0, 10.0));
+        let uv = Rect::from_min_max(pos2(0.5, 0.5), pos2(1.0, 1.0));
+
+        // A font texture mesh with both a plain and a textured (glyph-like) quad.
+        let mut mesh = Mesh::default();
+        mesh.add_colored_rect(rect, Color32::RED);
+        mesh.add_rect_with_uv(rect.translate(egui::vec2(10.0, 0.0)), uv, Color32::GREEN);
+        // A user texture (image) mesh.
+        let mut image_mesh = Mesh::with_texture(TextureId::User(0));
+        image_mesh.add_rect_with_uv(rect, uv, Color32::BLUE);
+        let paint_jobs = [
+            ClippedPrimitive {
+                clip_rect,
+                primitive: egui::epaint::Primitive::Mesh(mesh),
+            },
+            ClippedPrimitive {
+                clip_rect,
+                primitive: egui::epaint::Primitive::Mesh(image_mesh),
+            },
+        ];
+
+        let svg = paint_jobs_to_svg(&paint_jobs, false);
+        assert_eq!(svg.matches("<path").count(), 1);
+        assert!(svg.contains(r#"fill="rgb(255,0,0)""#));
+        assert!(!svg.contains(r#"fill="rgb(0,255,0)""#));
+        assert!(!svg.contains(r#"fill="rgb(0,0,255)""#));
+        // Infinite clip rects are limited to the mesh bounds.
+        assert!(svg.contains(r#"<rect x="0" y="0" width="20" height="10"/>"#));
+
+        let svg = paint_jobs_to_svg(&paint_jobs, true);
+        assert_eq!(svg.matches("<path").count(), 3);
+        assert!(svg.contains(r#"fill="rgb(0,255,0)""#));
+        assert!(svg.contains(r#"fill="rgb(0,0,255)""#));
+    }
+}
@@ -1,26 +1,29 @@
 use crate::{
     helpers::QueryHelper,
     render::{
-        DrawCommand, DrawPrimitive, EguiBevyPaintCallback, EguiCameraView, EguiDraw, EguiPipeline,
-        EguiPipelineKey, EguiViewTarget, PaintCallbackDraw,
+        DrawCommand, DrawPrimitive, EguiBevyPaintCallback, EguiCameraView, EguiDraw,
+        EguiImageTargetView, EguiPipeline, EguiPipelineKey, EguiViewContext, EguiViewTarget,
+        PaintCallbackDraw,
     },
-    EguiContextSettings, EguiManagedTextures, EguiRenderOutput, EguiUserTextures,
-    RenderComputedScaleFactor,
+    EguiContextSettings, EguiManagedTexturePartialUpdate, EguiManagedTexturePartialUpdates,
+    EguiManagedTextures, EguiRenderOutput, EguiUserTextureAlpha, EguiUserTextures,
+    RenderComputedScaleFactor, RenderEguiContextSettings,
 };
 use bevy_asset::prelude::*;
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{prelude::*, system::SystemParam};
 use bevy_image::Image;
 use bevy_log as log;
-use bevy_math::{URect, UVec2, Vec2};
+use bevy_math::{Mat3, URect, UVec2, Vec2};
 use bevy_platform::collections::HashMap;
 use bevy_render::{
-    camera::ExtractedCamera,
+    camera::{ExtractedCamera, NormalizedRenderTarget},
     extract_resource::ExtractResource,
     render_asset::RenderAssets,
     render_resource::{
         BindGroup, BindGroupEntry, BindingResource, Buffer, BufferDescriptor, BufferId,
-        CachedRenderPipelineId, DynamicUniformBuffer, PipelineCache, SpecializedRenderPipelines,
+        CachedRenderPipelineId, DynamicUniformBuffer, Extent3d, Origin3d, PipelineCache,
+        SpecializedRenderPipelines, TexelCopyBufferLayout, TexelCopyTextureInfo, TextureAspect,
     },
     renderer::{RenderDevice, RenderQueue},
     sync_world::{MainEntity, RenderEntity},
@@ -65,6 +68,9 @@ pub struct ExtractedEguiTextures<'w> {
 
 impl ExtractedEguiTextures<'_> {
     /// Returns an iterator over all textures (both Egui and Bevy managed).
+    ///
+    /// For user textures added with [`crate::EguiUserTextureAlpha::PremultiplyOnUpload`],
+    /// the premultiplied copies are returned once they are created.
     pub fn handles(&self) -> impl Iterator<Item = (EguiTextureId, AssetId<Image>)> + '_ {
         self.egui_textures
             .0
@@ -75,12 +81,29 @@ impl ExtractedEguiTextures<'_> {
                     managed_tex.id(),
                 )
             })
-            .chain(
-                self.user_textures
-                    .textures
-                    .iter()
-                    .map(|(handle, id)| (EguiTextureId::User(*id), handle.id())),
-            )
+            .chain(self.user_textures.textures.iter().map(|(handle, id)| {
+                let image = match self.user_textures.premultiplied_copy(handle.id()) {
+                    Some(copy) => copy.id(),
+                    None => handle.id(),
+                };
+                (EguiTextureId::User(*id), image)
+            }))
+    }
+
+    /// Returns `true` if the texture should be sampled as having premultiplied alpha.
+    pub fn is_premultiplied(&self, texture: &EguiTextureId, image: AssetId<Image>) -> bool {
+        let EguiTextureId::User(id) = texture else {
+            return false;
+        };
+        match self.user_textures.alpha_mode(*id) {
+            EguiUserTextureAlpha::Straight => false,
+            EguiUserTextureAlpha::Premultiplied => true,
+            // Is sampled as straight alpha until the premultiplied copy replaces the original image.
+            EguiUserTextureAlpha::PremultiplyOnUpload => !self
+                .user_textures
+                .textures
+                .contains_key(&Handle::Weak(image)),
+        }
     }
 }
 
@@ -101,8 +124,11 @@ pub struct EguiTransforms {
 pub struct EguiTransform {
     /// Is affected by render target size, scale factor and [`EguiContextSettings::scale_factor`].
     pub scale: Vec2,
-    /// Normally equals `Vec2::new(-1.0, 1.0)`.
+    /// Normally equals `Vec2::new(-1.0, 1.0)` (or `Vec2::new(-1.0, -1.0)` if [`EguiContextSettings::flip_y`] is enabled).
     pub translation: Vec2,
+    /// Is applied to Egui coordinates before the scale and translation, equals [`crate::EguiContextTransform`]
+    /// (or identity if the component is absent).
+    pub affine: Mat3,
 }
 
 impl EguiTransform {
@@ -114,6 +140,7 @@ impl EguiTransform {
                 -2.0 / (target_size.y / scale_factor),
             ),
             translation: Vec2::new(-1.0, 1.0),
+            affine: Mat3::IDENTITY,
         }
     }
 }
@@ -121,8 +148,9 @@ impl EguiTransform {
 /// Prepares Egui transforms.
 pub fn prepare_egui_transforms_system(
     mut egui_transforms: ResMut<EguiTransforms>,
-    views: Query<&RenderComputedScaleFactor>,
+    views: Query<(&RenderComputedScaleFactor, &RenderEguiContextSettings)>,
     render_targets: Query<(&ExtractedView, &ExtractedCamera, &EguiCameraView)>,
+    image_targets: Query<(Entity, &ExtractedView, &EguiImageTargetView)>,
     render_device: Res<RenderDevice>,
     render_queue: Res<RenderQueue>,
     egui_pipeline: Res<EguiPipeline>,
@@ -130,15 +158,23 @@ pub fn prepare_egui_transforms_system(
     egui_transforms.buffer.clear();
     egui_transforms.offsets.clear();
 
-    for (view, camera, egui_camera_view) in render_targets.iter() {
-        let Some(target_size) = camera.physical_target_size else {
-            continue;
-        };
-
-        let &RenderComputedScaleFactor { scale_factor } = views.get(egui_camera_view.0)?;
-        let offset = egui_transforms
-            .buffer
-            .push(&EguiTransform::new(target_size.as_vec2(), scale_factor));
+    let camera_targets = render_targets
+        .iter()
+        .filter_map(|(view, camera, egui_camera_view)| {
+            Some((view, camera.physical_target_size?, egui_camera_view.0))
+        });
+    let image_targets = image_targets
+        .iter()
+        .map(|(egui_view, view, image_target)| (view, image_target.size, egui_view));
+    for (view, target_size, egui_view) in camera_targets.chain(image_targets) {
+        let (&RenderComputedScaleFactor { scale_factor }, settings) = views.get(egui_view)?;
+        let mut transform = EguiTransform::new(target_size.as_vec2(), scale_factor);
+        if settings.flip_y {
+            transform.scale.y = -transform.scale.y;
+            transform.translation.y = -transform.translation.y;
+        }
+        transform.affine = settings.transform;
+        let offset = egui_transforms.buffer.push(&transform);
         egui_transforms
             .offsets
             .insert(view.retained_view_entity.main_entity, offset);
@@ -168,6 +204,74 @@ pub fn prepare_egui_transforms_system(
     Ok(())
 }
 
+/// Writes partial updates of Egui managed textures directly into the respective GPU textures.
+///
+/// Updates of textures that aren't uploaded to GPU yet are kept until the textures are prepared.
+pub fn write_egui_managed_texture_partial_updates_system(
+    mut partial_updates: ResMut<EguiManagedTexturePartialUpdates>,
+    mut pending_updates: Local<Vec<EguiManagedTexturePartialUpdate>>,
+    gpu_images: Res<RenderAssets<GpuImage>>,
+    render_queue: Res<RenderQueue>,
+) {
+    // The resource persists in the render world if it doesn't change in the main one,
+    // so we make sure not to apply the same updates twice.
+    let updates = std::mem::take(&mut *pending_updates)
+        .into_iter()
+        .chain(partial_updates.drain(..))
+        .collect::<Vec<_>>();
+    for update in updates {
+        // Pending updates hold strong handles, so the images can't be freed before they are applied.
+        // The updates of an image have to be written in order, so the following ones are queued as well.
+        let Some(gpu_image) = gpu_images.get(&update.handle).filter(|_| {
+            !pending_updates
+                .iter()
+                .any(|pending_update| pending_update.handle == update.handle)
+        }) else {
+            log::debug!(
+                "Delaying a partial update of a texture that isn't uploaded to GPU (handle: {:?})",
+                update.handle
+            );
+            pending_updates.push(update);
+            continue;
+        };
+
+        let [x, y] = update.pos;
+        let width = update.color_image.width() as u32;
+        let height = update.color_image.height() as u32;
+        // Egui textures are stored unmultiplied, see `color_image_as_bevy_image`.
+        let pixels: Vec<u8> = update
+            .color_image
+            .pixels
+            .iter()
+            .flat_map(|color| color.to_srgba_unmultiplied())
+            .collect();
+
+        render_queue.write_texture(
+            TexelCopyTextureInfo {
+                texture: &gpu_image.texture,
+                mip_level: 0,
+                origin: Origin3d {
+                    x: x as u32,
+                    y: y as u32,
+                    z: 0,
+                },
+                aspect: TextureAspect::All,
+            },
+            &pixels,
+            TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(4 * width),
+                rows_per_image: None,
+            },
+            Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+    }
+}
+
 /// Maps Egui textures to bind groups.
 #[derive(Resource, Deref, DerefMut, Default)]
 pub struct EguiTextureBindGroups(pub HashMap<EguiTextureId, BindGroup>);
@@ -184,6 +288,11 @@ pub fn queue_bind_groups_system(
         .handles()
         .filter_map(|(texture, handle_id)| {
             let gpu_image = gpu_images.get(&Handle::Weak(handle_id))?;
+            let texture_params = if egui_textures.is_premultiplied(&texture, handle_id) {
+                &egui_pipeline.premultiplied_alpha_texture_params
+            } else {
+                &egui_pipeline.straight_alpha_texture_params
+            };
             let bind_group = render_device.create_bind_group(
                 None,
                 &egui_pipeline.texture_bind_group_layout,
@@ -196,6 +305,10 @@ pub fn queue_bind_groups_system(
                         binding: 1,
                         resource: BindingResource::Sampler(&gpu_image.sampler),
                     },
+                    BindGroupEntry {
+                        binding: 2,
+                        resource: texture_params.as_entire_binding(),
+                    },
                 ],
             );
             Some((texture, bind_group))
@@ -205,6 +318,24 @@ pub fn queue_bind_groups_system(
     commands.insert_resource(EguiTextureBindGroups(bind_groups))
 }
 
+/// Queues [`EguiPipeline`] instances for all the possible [`EguiPipelineKey`] values once,
+/// see [`crate::EguiPlugin::prewarm_pipelines`].
+pub fn prewarm_pipelines_system(
+    mut prewarmed: Local<bool>,
+    pipeline_cache: Res<PipelineCache>,
+    mut specialized_pipelines: ResMut<SpecializedRenderPipelines<EguiPipeline>>,
+    egui_pipeline: Res<EguiPipeline>,
+) {
+    if *prewarmed {
+        return;
+    }
+    *prewarmed = true;
+
+    for key in EguiPipelineKey::all() {
+        specialized_pipelines.specialize(&pipeline_cache, &egui_pipeline, key);
+    }
+}
+
 /// Cached Pipeline IDs for the specialized instances of `EguiPipeline`.
 #[derive(Resource)]
 pub struct EguiPipelines(pub HashMap<MainEntity, CachedRenderPipelineId>);
@@ -215,22 +346,41 @@ pub fn queue_pipelines_system(
     pipeline_cache: Res<PipelineCache>,
     mut specialized_pipelines: ResMut<SpecializedRenderPipelines<EguiPipeline>>,
     egui_pipeline: Res<EguiPipeline>,
-    egui_views: Query<&EguiViewTarget, With<ExtractedView>>,
+    egui_views: Query<(&EguiViewTarget, &RenderEguiContextSettings), With<ExtractedView>>,
     camera_views: Query<(&MainEntity, &ExtractedCamera)>,
+    image_target_views: Query<(
+        &ExtractedView,
+        &EguiImageTargetView,
+        &RenderEguiContextSettings,
+    )>,
 ) {
-    let pipelines: HashMap<MainEntity, CachedRenderPipelineId> = egui_views
+    let camera_targets = egui_views
         .iter()
-        .filter_map(|egui_camera_view| {
+        .filter_map(|(egui_camera_view, settings)| {
             let (main_entity, extracted_camera) = camera_views.get_some(egui_camera_view.0)?;
-
+            Some((*main_entity, extracted_camera.hdr, settings))
+        });
+    let image_targets = image_target_views
+        .iter()
+        .map(|(view, image_target, settings)| {
+            (
+                view.retained_view_entity.main_entity,
+                image_target.hdr,
+                settings,
+            )
+        });
+    let pipelines: HashMap<MainEntity, CachedRenderPipelineId> = camera_targets
+        .chain(image_targets)
+        .map(|(main_entity, hdr, settings)| {
             let pipeline_id = specialized_pipelines.specialize(
                 &pipeline_cache,
                 &egui_pipeline,
                 EguiPipelineKey {
-                    hdr: extracted_camera.hdr,
+                    hdr,
+                    color_space: settings.color_space,
                 },
             );
-            Some((*main_entity, pipeline_id))
+            (main_entity, pipeline_id)
         })
         .collect();
 
@@ -277,6 +427,45 @@ impl Default for EguiRenderTargetData {
     }
 }
 
+/// Builds a quad filled with the background color that covers the viewport (in physical pixels)
+/// once the context transform is applied, returns it together with its clip rectangle.
+///
+/// The quad samples the white texel of the font texture (managed texture `0`), like Egui's own fills.
+fn background_mesh(
+    viewport_rect: URect,
+    pixels_per_point: f32,
+    transform: Mat3,
+    color: egui::Color32,
+) -> Option<(egui::Rect, egui::Mesh)> {
+    if viewport_rect.is_empty() || pixels_per_point <= 0.0 || transform.determinant() == 0.0 {
+        return None;
+    }
+    let inverse_transform = transform.inverse();
+    let viewport_rect = viewport_rect.as_rect();
+    let corners = [
+        viewport_rect.min,
+        Vec2::new(viewport_rect.max.x, viewport_rect.min.y),
+        viewport_rect.max,
+        Vec2::new(viewport_rect.min.x, viewport_rect.max.y),
+    ]
+    .map(|corner| {
+        let corner = inverse_transform.transform_point2(corner / pixels_per_point);
+        egui::pos2(corner.x, corner.y)
+    });
+
+    let mut mesh = egui::Mesh::default();
+    for pos in corners {
+        mesh.vertices.push(egui::epaint::Vertex {
+            pos,
+            uv: egui::epaint::WHITE_UV,
+            color,
+        });
+    }
+    mesh.add_triangle(0, 1, 2);
+    mesh.add_triangle(0, 2, 3);
+    Some((egui::Rect::from_points(&corners), mesh))
+}
+
 /// Prepares Egui transforms.
 pub fn prepare_egui_render_target_data_system(
     mut render_data: ResMut<EguiRenderData>,
@@ -284,7 +473,10 @@ pub fn prepare_egui_render_target_data_system(
         Entity,
         &ExtractedView,
         &RenderComputedScaleFactor,
-        &EguiViewTarget,
+        &RenderEguiContextSettings,
+        Option<&EguiViewTarget>,
+        Option<&EguiImageTargetView>,
+        &EguiViewContext,
         &EguiRenderOutput,
     )>,
     extracted_cameras: Query<&ExtractedCamera>,
@@ -298,8 +490,16 @@ pub fn prepare_egui_render_target_data_system(
         keep
     });
 
-    for (render_entity, view, computed_scale_factor, egui_view_target, render_output) in
-        render_targets.iter()
+    for (
+        render_entity,
+        view,
+        computed_scale_factor,
+        settings,
+        egui_view_target,
+        egui_image_target_view,
+        egui_view_context,
+        render_output,
+    ) in render_targets.iter()
     {
         let data = render_data
             .entry(view.retained_view_entity.main_entity)
@@ -309,19 +509,37 @@ pub fn prepare_egui_render_target_data_system(
         data.render_entity = render_entity.into();
 
         // Construct a pipeline key based on a render target.
-        let Ok(extracted_camera) = extracted_cameras.get(egui_view_target.0) else {
-            log::warn!("ExtractedCamera entity doesn't exist for the Egui view");
-            continue;
-        };
+        let (hdr, viewport_size, renders_to_image) =
+            match (egui_view_target, egui_image_target_view) {
+                (Some(egui_view_target), _) => {
+                    let Ok(extracted_camera) = extracted_cameras.get(egui_view_target.0) else {
+                        log::warn!("ExtractedCamera entity doesn't exist for the Egui view");
+                        continue;
+                    };
+                    (
+                        extracted_camera.hdr,
+                        extracted_camera.physical_viewport_size,
+                        matches!(
+                            extracted_camera.target,
+                            Some(NormalizedRenderTarget::Image(_))
+                        ),
+                    )
+                }
+                // Image target views are cleared with the background color by the pass itself.
+                (None, Some(egui_image_target_view)) => (
+                    egui_image_target_view.hdr,
+                    Some(egui_image_target_view.size),
+                    false,
+                ),
+                (None, None) => continue,
+            };
         data.key = Some(EguiPipelineKey {
-            hdr: extracted_camera.hdr,
+            hdr,
+            color_space: settings.color_space,
         });
 
         data.pixels_per_point = computed_scale_factor.scale_factor;
-        if extracted_camera
-            .physical_viewport_size
-            .map_or(true, |size| size.x < 1 || size.y < 1)
-        {
+        if viewport_size.map_or(true, |size| size.x < 1 || size.y < 1) {
             continue;
         }
 
@@ -332,6 +550,36 @@ pub fn prepare_egui_render_target_data_system(
         data.index_data.clear();
         data.postponed_updates.clear();
 
+        let viewport_rect = URect::new(
+            view.viewport.x,
+            view.viewport.y,
+            view.viewport.x + view.viewport.z,
+            view.viewport.y + view.viewport.w,
+        );
+
+        // The camera has already rendered into its target, so instead of clearing it,
+        // the background is painted before the paint jobs and confined to the camera viewport.
+        if let (Some(background), true) = (settings.render_background, renders_to_image) {
+            if let Some((clip_rect, mesh)) = background_mesh(
+                viewport_rect,
+                data.pixels_per_point,
+                settings.transform,
+                background,
+            ) {
+                data.vertex_data
+                    .extend_from_slice(cast_slice::<_, u8>(mesh.vertices.as_slice()));
+                data.index_data.extend(mesh.indices.iter().copied());
+                index_offset += mesh.vertices.len() as u32;
+                data.draw_commands.push(DrawCommand {
+                    primitive: DrawPrimitive::Egui(EguiDraw {
+                        vertices_count: mesh.indices.len(),
+                        egui_texture: EguiTextureId::Managed(egui_view_context.0, 0),
+                    }),
+                    clip_rect,
+                });
+            }
+        }
+
         for egui::epaint::ClippedPrimitive {
             clip_rect,
             primitive,
@@ -350,15 +598,7 @@ pub fn prepare_egui_render_target_data_system(
                 },
             };
 
-            if clip_urect
-                .intersect(URect::new(
-                    view.viewport.x,
-                    view.viewport.y,
-                    view.viewport.x + view.viewport.z,
-                    view.viewport.y + view.viewport.w,
-                ))
-                .is_empty()
-            {
+            if clip_urect.intersect(viewport_rect).is_empty() {
                 continue;
             }
 
@@ -403,9 +643,7 @@ pub fn prepare_egui_render_target_data_system(
             index_offset += mesh.vertices.len() as u32;
 
             let texture_handle = match mesh.texture_id {
-                egui::TextureId::Managed(id) => {
-                    EguiTextureId::Managed(view.retained_view_entity.main_entity, id)
-                }
+                egui::TextureId::Managed(id) => EguiTextureId::Managed(egui_view_context.0, id),
                 egui::TextureId::User(id) => EguiTextureId::User(id),
             };
 
@@ -0,0 +1,190 @@
+use crate::{EguiContext, EguiContextMirror};
+use bevy_asset::{Assets, Handle};
+use bevy_core_pipeline::core_2d::Camera2d;
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    event::{BufferedEvent, Event, EventReader, EventWriter},
+    observer::Trigger,
+    query::With,
+    system::{Commands, Query, ResMut},
+};
+use bevy_image::{BevyDefault, Image};
+use bevy_render::{
+    camera::{Camera, ClearColorConfig, RenderTarget},
+    view::{
+        screenshot::{Screenshot, ScreenshotCaptured},
+        RenderLayers,
+    },
+};
+use wgpu_types::{Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages};
+
+/// The number of frames a screenshot camera renders before the capture,
+/// which gives the render world a chance to prepare the Egui view of the new camera.
+const FRAMES_BEFORE_CAPTURE: u32 = 2;
+
+/// Requests a screenshot of the render output of an Egui context (e.g. to export a panel as PNG or to attach it to a bug report).
+///
+/// The context output is rendered to an offscreen image on its own, without the scene beneath it
+/// (see [`EguiContextMirror`]). The result is delivered with [`EguiScreenshotReadyEvent`] a few frames later.
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use bevy_egui::{screenshot::{EguiScreenshotReadyEvent, EguiScreenshotRequestEvent}, PrimaryEguiContext};
+/// fn request_screenshot_system(
+///     primary_context: Single<Entity, With<PrimaryEguiContext>>,
+///     mut screenshot_requests: EventWriter<EguiScreenshotRequestEvent>,
+/// ) {
+///     screenshot_requests.write(EguiScreenshotRequestEvent {
+///         context: *primary_context,
+///     });
+/// }
+///
+/// fn read_screenshot_system(mut screenshots: EventReader<EguiScreenshotReadyEvent>) {
+///     for screenshot in screenshots.read() {
+///         info!("Captured a {:?} screenshot", screenshot.image.size());
+///     }
+/// }
+/// ```
+///
+/// If the Egui pipelines aren't compiled by the time of the capture, the screenshot may be empty,
+/// consider enabling [`crate::EguiPlugin::prewarm_pipelines`].
+#[derive(Event, BufferedEvent, Clone, Debug)]
+pub struct EguiScreenshotRequestEvent {
+    /// The context to capture, must be a camera entity with [`EguiContext`].
+    pub context: Entity,
+}
+
+/// Is sent when a screenshot requested with [`EguiScreenshotRequestEvent`] is captured.
+#[derive(Event, BufferedEvent, Clone, Debug)]
+pub struct EguiScreenshotReadyEvent {
+    /// The captured context.
+    pub context: Entity,
+    /// The captured image, its size matches the physical viewport size of the context camera.
+    pub image: Image,
+}
+
+/// A camera that renders an Egui context for a screenshot, see [`EguiScreenshotRequestEvent`].
+///
+/// The camera is despawned once the screenshot is captured.
+#[derive(Component, Debug)]
+pub struct EguiScreenshotCamera {
+    context: Entity,
+    image: Handle<Image>,
+    frames_before_capture: u32,
+}
+
+/// Spawns [`EguiScreenshotCamera`] cameras for [`EguiScreenshotRequestEvent`] events.
+pub fn spawn_egui_screenshot_cameras_system(
+    mut commands: Commands,
+    mut screenshot_requests: EventReader<EguiScreenshotRequestEvent>,
+    contexts: Query<&Camera, With<EguiContext>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    for request in screenshot_requests.read() {
+        let Ok(camera) = contexts.get(request.context) else {
+            bevy_log::warn!(
+                "Can't take a screenshot of a context ({:?}), as it doesn't exist or isn't a camera",
+                request.context
+            );
+            continue;
+        };
+        let Some(viewport_size) = camera
+            .physical_viewport_size()
+            .filter(|size| size.x > 0 && size.y > 0)
+        else {
+            bevy_log::warn!(
+                "Can't take a screenshot of a context ({:?}) with an empty viewport",
+                request.context
+            );
+            continue;
+        };
+
+        let size = Extent3d {
+            width: viewport_size.x,
+            height: viewport_size.y,
+            ..Default::default()
+        };
+        let mut image = Image {
+            texture_descriptor: TextureDescriptor {
+                label: Some("egui_screenshot"),
+                size,
+                dimension: TextureDimension::D2,
+                // Matches the format of the non-HDR Egui pipeline.
+                format: TextureFormat::bevy_default(),
+                mip_level_count: 1,
+                sample_count: 1,
+                usage: TextureUsages::TEXTURE_BINDING
+                    | TextureUsages::COPY_SRC
+                    | TextureUsages::COPY_DST
+                    | TextureUsages::RENDER_ATTACHMENT,
+                view_formats: &[],
+            },
+            ..Default::default()
+        };
+        // Fill the image with transparent pixels, as the camera doesn't clear it.
+        image.resize(size);
+        let image = images.add(image);
+
+        commands.spawn((
+            Camera2d,
+            Camera {
+                target: RenderTarget::Image(image.clone().into()),
+                clear_color: ClearColorConfig::None,
+                ..Default::default()
+            },
+            // Render nothing but Egui.
+            RenderLayers::none(),
+            EguiContextMirror(request.context),
+            EguiScreenshotCamera {
+                context: request.context,
+                image,
+                frames_before_capture: FRAMES_BEFORE_CAPTURE,
+            },
+        ));
+    }
+}
+
+/// Captures images rendered by [`EguiScreenshotCamera`] cameras and sends [`EguiScreenshotReadyEvent`] events.
+pub fn capture_egui_screenshots_system(
+    mut commands: Commands,
+    mut screenshot_cameras: Query<(Entity, &mut EguiScreenshotCamera)>,
+    contexts: Query<(), With<EguiContext>>,
+) {
+    for (camera_entity, mut screenshot_camera) in screenshot_cameras.iter_mut() {
+        if !contexts.contains(screenshot_camera.context) {
+            bevy_log::warn!(
+                "Egui context ({:?}) was removed before the screenshot was captured",
+                screenshot_camera.context
+            );
+            commands.entity(camera_entity).despawn();
+            continue;
+        }
+
+        // The capture has already been requested.
+        if screenshot_camera.frames_before_capture == 0 {
+            continue;
+        }
+        screenshot_camera.frames_before_capture -= 1;
+        if screenshot_camera.frames_before_capture > 0 {
+            continue;
+        }
+
+        let context = screenshot_camera.context;
+        commands
+            .spawn(Screenshot::image(screenshot_camera.image.clone()))
+            .observe(
+                move |trigger: Trigger<ScreenshotCaptured>,
+                      mut commands: Commands,
+                      mut screenshot_ready_writer: EventWriter<EguiScreenshotReadyEvent>| {
+                    screenshot_ready_writer.write(EguiScreenshotReadyEvent {
+                        context,
+                        image: trigger.event().0.clone(),
+                    });
+                    if let Ok(mut camera_commands) = commands.get_entity(camera_entity) {
+                        camera_commands.despawn();
+                    }
+                },
+            );
+    }
+}
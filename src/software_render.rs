@@ -0,0 +1,154 @@
+use crate::EguiRenderOutput;
+use bevy_platform::collections::HashMap;
+
+/// Stores Egui textures for [`blit`], mirroring the textures that the GPU renderer manages.
+///
+/// Call [`EguiSoftwareTextures::set`] before and [`EguiSoftwareTextures::free`] after blitting
+/// each [`EguiRenderOutput`] to keep the textures in sync with Egui.
+#[derive(Clone, Debug, Default)]
+pub struct EguiSoftwareTextures {
+    textures: HashMap<egui::TextureId, egui::ColorImage>,
+}
+
+impl EguiSoftwareTextures {
+    /// Applies the texture updates (full and partial ones) of [`EguiRenderOutput::textures_delta`].
+    pub fn set(&mut self, textures_delta: &egui::TexturesDelta) {
+        for (texture_id, image_delta) in &textures_delta.set {
+            let egui::ImageData::Color(image) = &image_delta.image;
+            let Some(pos) = image_delta.pos else {
+                self.textures.insert(*texture_id, (**image).clone());
+                continue;
+            };
+
+            // Partial update.
+            let Some(texture) = self.textures.get_mut(texture_id) else {
+                bevy_log::warn!("Partial update of a missing texture (id: {:?})", texture_id);
+                continue;
+            };
+            let [texture_width, texture_height] = texture.size;
+            for y in 0..image.height().min(texture_height.saturating_sub(pos[1])) {
+                let src = &image.pixels[y * image.width()..][..image.width()];
+                let dst = &mut texture.pixels[(pos[1] + y) * texture_width + pos[0]..];
+                let len = src.len().min(texture_width.saturating_sub(pos[0]));
+                dst[..len].copy_from_slice(&src[..len]);
+            }
+        }
+    }
+
+    /// Removes the textures freed in [`EguiRenderOutput::textures_delta`].
+    pub fn free(&mut self, textures_delta: &egui::TexturesDelta) {
+        for texture_id in &textures_delta.free {
+            self.textures.remove(texture_id);
+        }
+    }
+
+    /// Adds a user texture, which can be referenced in Egui with [`egui::TextureId::User`].
+    pub fn insert_user_texture(&mut self, id: u64, image: egui::ColorImage) {
+        self.textures.insert(egui::TextureId::User(id), image);
+    }
+
+    /// Removes a user texture.
+    pub fn remove_user_texture(&mut self, id: u64) -> Option<egui::ColorImage> {
+        self.textures.remove(&egui::TextureId::User(id))
+    }
+}
+
+/// Rasterizes the paint jobs of [`EguiRenderOutput`] into an RGBA (8 bits per channel, sRGB) buffer.
+///
+/// `stride` is the number of bytes per row of `target`, the width of the target is `stride / 4`,
+/// and the height is `target.len() / stride`. Shapes are blended over the existing contents of the buffer.
+///
+/// This is a slow, CPU-only renderer meant for targets without [`bevy_render`] (e.g. custom framebuffers).
+/// Textures are sampled with the nearest filter, and paint callbacks are ignored.
+pub fn blit(
+    output: &EguiRenderOutput,
+    textures: &EguiSoftwareTextures,
+    pixels_per_point: f32,
+    target: &mut [u8],
+    stride: usize,
+) {
+    let width = stride / 4;
+    if width == 0 {
+        return;
+    }
+    let height = target.len() / stride;
+
+    for egui::ClippedPrimitive {
+        clip_rect,
+        primitive,
+    } in &output.paint_jobs
+    {
+        let egui::epaint::Primitive::Mesh(mesh) = primitive else {
+            continue;
+        };
+        let Some(texture) = textures.textures.get(&mesh.texture_id) else {
+            continue;
+        };
+
+        let clip_min_x =
+            ((clip_rect.min.x * pixels_per_point).round().max(0.0) as usize).min(width);
+        let clip_min_y =
+            ((clip_rect.min.y * pixels_per_point).round().max(0.0) as usize).min(height);
+        let clip_max_x =
+            ((clip_rect.max.x * pixels_per_point).round().max(0.0) as usize).min(width);
+        let clip_max_y =
+            ((clip_rect.max.y * pixels_per_point).round().max(0.0) as usize).min(height);
+
+        for triangle in mesh.indices.chunks_exact(3) {
+            let [a, b, c] = [0, 1, 2].map(|i| &mesh.vertices[triangle[i] as usize]);
+            let [pa, pb, pc] = [a, b, c].map(|v| v.pos * pixels_per_point);
+
+            let area = edge(pa, pb, pc);
+            if area == 0.0 {
+                continue;
+            }
+
+            let min_x = (pa.x.min(pb.x).min(pc.x).floor().max(0.0) as usize).max(clip_min_x);
+            let min_y = (pa.y.min(pb.y).min(pc.y).floor().max(0.0) as usize).max(clip_min_y);
+            let max_x = (pa.x.max(pb.x).max(pc.x).ceil().max(0.0) as usize).min(clip_max_x);
+            let max_y = (pa.y.max(pb.y).max(pc.y).ceil().max(0.0) as usize).min(clip_max_y);
+
+            for y in min_y..max_y {
+                for x in min_x..max_x {
+                    let p = egui::pos2(x as f32 + 0.5, y as f32 + 0.5);
+                    let wa = edge(pb, pc, p) / area;
+                    let wb = edge(pc, pa, p) / area;
+                    let wc = edge(pa, pb, p) / area;
+                    if wa < 0.0 || wb < 0.0 || wc < 0.0 {
+                        continue;
+                    }
+
+                    let uv = a.uv.to_vec2() * wa + b.uv.to_vec2() * wb + c.uv.to_vec2() * wc;
+                    // Egui interpolates and multiplies premultiplied colors in gamma space.
+                    let [ca, cb, cc] = [a, b, c].map(|v| v.color.to_array());
+                    let vertex_color = [0, 1, 2, 3]
+                        .map(|i| ca[i] as f32 * wa + cb[i] as f32 * wb + cc[i] as f32 * wc);
+                    let texel = sample_nearest(texture, uv).to_array();
+                    let src =
+                        [0, 1, 2, 3].map(|i| vertex_color[i] / 255.0 * (texel[i] as f32 / 255.0));
+
+                    let src_alpha = src[3];
+                    let pixel = &mut target[y * stride + x * 4..][..4];
+                    for (dst, src) in pixel.iter_mut().zip(src) {
+                        let blended = src + *dst as f32 / 255.0 * (1.0 - src_alpha);
+                        *dst = (blended * 255.0).round().clamp(0.0, 255.0) as u8;
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn edge(a: egui::Pos2, b: egui::Pos2, p: egui::Pos2) -> f32 {
+    (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x)
+}
+
+fn sample_nearest(texture: &egui::ColorImage, uv: egui::Vec2) -> egui::Color32 {
+    let [texture_width, texture_height] = texture.size;
+    if texture_width == 0 || texture_height == 0 {
+        return egui::Color32::TRANSPARENT;
+    }
+    let x = ((uv.x * texture_width as f32) as usize).min(texture_width - 1);
+    let y = ((uv.y * texture_height as f32) as usize).min(texture_height - 1);
+    texture.pixels[y * texture_width + x]
+}
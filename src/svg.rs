@@ -0,0 +1,103 @@
+use bevy_asset::Handle;
+use bevy_ecs::prelude::Resource;
+use bevy_image::{Image, ImageSampler};
+use bevy_math::UVec2;
+use bevy_platform::collections::HashMap;
+
+/// An error returned by [`crate::EguiContexts::add_svg`].
+#[derive(Debug)]
+pub enum EguiSvgError {
+    /// The SVG data couldn't be parsed.
+    Parse(resvg::usvg::Error),
+    /// The requested size has a zero dimension.
+    EmptySize,
+    /// The [`bevy_asset::AssetServer`] resource doesn't exist, so the rasterized image can't be added.
+    MissingAssetServer,
+}
+
+impl std::fmt::Display for EguiSvgError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parse(err) => write!(f, "failed to parse SVG: {err}"),
+            Self::EmptySize => write!(f, "SVG can't be rasterized with an empty size"),
+            Self::MissingAssetServer => write!(f, "AssetServer resource doesn't exist"),
+        }
+    }
+}
+
+impl std::error::Error for EguiSvgError {}
+
+/// A resource caching the images rasterized by [`crate::EguiContexts::add_svg`].
+///
+/// Unlike [`crate::EguiUserTextures`], it isn't extracted, so the SVG data stays in the main world.
+/// The cache is keyed by the full SVG data (rather than its hash) to rule out collisions.
+#[derive(Resource, Default)]
+pub struct EguiSvgCache {
+    images: HashMap<Vec<u8>, HashMap<UVec2, Handle<Image>>>,
+}
+
+impl EguiSvgCache {
+    /// Returns the image rasterized from the SVG data with the given size.
+    pub fn get(&self, bytes: &[u8], size: UVec2) -> Option<Handle<Image>> {
+        self.images
+            .get(bytes)
+            .and_then(|sizes| sizes.get(&size))
+            .cloned()
+    }
+
+    /// Stores the image rasterized from the SVG data with the given size.
+    pub fn insert(&mut self, bytes: &[u8], size: UVec2, image: Handle<Image>) {
+        self.images
+            .entry(bytes.to_vec())
+            .or_default()
+            .insert(size, image);
+    }
+
+    /// Removes the image rasterized from the SVG data with the given size and returns it.
+    pub fn remove(&mut self, bytes: &[u8], size: UVec2) -> Option<Handle<Image>> {
+        let sizes = self.images.get_mut(bytes)?;
+        let image = sizes.remove(&size)?;
+        if sizes.is_empty() {
+            self.images.remove(bytes);
+        }
+        Some(image)
+    }
+}
+
+/// Rasterizes SVG data into an image of the given size (in pixels), the SVG is stretched to fit it.
+///
+/// Text elements aren't rendered, as no fonts are loaded, convert text to paths to display it.
+pub fn rasterize_svg(bytes: &[u8], size: UVec2) -> Result<Image, EguiSvgError> {
+    let tree = resvg::usvg::Tree::from_data(bytes, &resvg::usvg::Options::default())
+        .map_err(EguiSvgError::Parse)?;
+    let mut pixmap =
+        resvg::tiny_skia::Pixmap::new(size.x, size.y).ok_or(EguiSvgError::EmptySize)?;
+    let tree_size = tree.size();
+    resvg::render(
+        &tree,
+        resvg::tiny_skia::Transform::from_scale(
+            size.x as f32 / tree_size.width(),
+            size.y as f32 / tree_size.height(),
+        ),
+        &mut pixmap.as_mut(),
+    );
+
+    // Pixmap pixels are premultiplied, `color_image_as_bevy_image` unmultiplies them.
+    let pixels = pixmap
+        .pixels()
+        .iter()
+        .map(|pixel| {
+            egui::Color32::from_rgba_premultiplied(
+                pixel.red(),
+                pixel.green(),
+                pixel.blue(),
+                pixel.alpha(),
+            )
+        })
+        .collect();
+    let color_image = egui::ColorImage::new([size.x as usize, size.y as usize], pixels);
+    Ok(crate::render::color_image_as_bevy_image(
+        &color_image,
+        ImageSampler::Default,
+    ))
+}
@@ -0,0 +1,283 @@
+use crate::{
+    input::EguiInputEvent, EguiContext, EguiContextSettings, EguiGlobalSettings,
+    EguiOffscreenContext, EguiOutput, EguiPlugin, EguiPrimaryContextPass, EguiWantsInput,
+    PrimaryEguiContext,
+};
+use bevy_app::{App, PluginsState, TaskPoolPlugin};
+use bevy_ecs::{entity::Entity, schedule::IntoScheduleConfigs, system::ScheduleSystem};
+use bevy_math::Vec2;
+
+/// A headless [`App`] with [`EguiPlugin`] and a single offscreen primary Egui context,
+/// meant for testing UI logic.
+///
+/// UI systems added with [`EguiTestApp::add_ui_systems`] run in the [`EguiPrimaryContextPass`] schedule,
+/// input can be simulated with [`EguiTestApp::push_event`].
+///
+/// ```rust
+/// # use bevy_egui::{egui, test::EguiTestApp, EguiContexts};
+/// fn ui_system(mut contexts: EguiContexts) -> bevy::prelude::Result {
+///     egui::CentralPanel::default().show(contexts.ctx_mut()?, |ui| {
+///         ui.label("Hello world!");
+///     });
+///     Ok(())
+/// }
+///
+/// let mut app = EguiTestApp::new();
+/// app.add_ui_systems(ui_system);
+/// app.push_event(egui::Event::Text("a".to_owned())).update();
+/// assert!(!app.wants_input().wants_keyboard_input());
+/// ```
+pub struct EguiTestApp {
+    app: App,
+    context: Entity,
+}
+
+impl Default for EguiTestApp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EguiTestApp {
+    /// Creates an app with a 800x600 context (in logical pixels) and the scale factor of `1.0`.
+    pub fn new() -> Self {
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            bevy_time::TimePlugin,
+            bevy_input::InputPlugin,
+            bevy_window::WindowPlugin {
+                primary_window: None,
+                exit_condition: bevy_window::ExitCondition::DontExit,
+                close_when_requested: false,
+                ..Default::default()
+            },
+        ));
+        #[cfg(feature = "render")]
+        {
+            use bevy_asset::AssetApp;
+
+            app.add_plugins(bevy_asset::AssetPlugin::default());
+            app.init_asset::<bevy_image::Image>();
+            app.init_asset::<bevy_render::render_resource::Shader>();
+        }
+        app.insert_resource(EguiGlobalSettings {
+            auto_create_primary_context: false,
+            ..Default::default()
+        });
+        app.add_plugins(EguiPlugin::default());
+
+        let context = app
+            .world_mut()
+            .spawn((
+                EguiOffscreenContext::new(Vec2::new(800.0, 600.0)),
+                PrimaryEguiContext,
+            ))
+            .id();
+
+        Self { app, context }
+    }
+
+    /// Sets the logical screen size of the context.
+    pub fn with_screen_size(mut self, size: Vec2) -> Self {
+        self.offscreen_context_mut().size = size;
+        self
+    }
+
+    /// Sets the scale factor of the context.
+    pub fn with_scale_factor(mut self, scale_factor: f32) -> Self {
+        self.offscreen_context_mut().scale_factor = scale_factor;
+        self
+    }
+
+    /// Adds systems to the [`EguiPrimaryContextPass`] schedule.
+    pub fn add_ui_systems<M>(
+        &mut self,
+        systems: impl IntoScheduleConfigs<ScheduleSystem, M>,
+    ) -> &mut Self {
+        self.app.add_systems(EguiPrimaryContextPass, systems);
+        self
+    }
+
+    /// Sends an Egui event to the context, it will be processed during the next [`EguiTestApp::update`].
+    pub fn push_event(&mut self, event: egui::Event) -> &mut Self {
+        self.app.world_mut().write_event(EguiInputEvent {
+            context: self.context,
+            event,
+        });
+        self
+    }
+
+    /// Runs a single app update (i.e. a single Egui pass for the context).
+    pub fn update(&mut self) -> &mut Self {
+        if self.app.plugins_state() == PluginsState::Ready {
+            self.app.finish();
+            self.app.cleanup();
+        }
+        self.app.update();
+        self
+    }
+
+    /// Returns the output of the last Egui pass.
+    pub fn output(&self) -> &EguiOutput {
+        self.app
+            .world()
+            .get::<EguiOutput>(self.context)
+            .expect("Egui context must exist")
+    }
+
+    /// Returns whether the context wanted input during the last Egui pass.
+    pub fn wants_input(&self) -> &EguiWantsInput {
+        self.app
+            .world()
+            .get::<EguiWantsInput>(self.context)
+            .expect("Egui context must exist")
+    }
+
+    /// Returns the Egui context, can be used to inspect Egui memory.
+    pub fn ctx_mut(&mut self) -> &mut egui::Context {
+        self.app
+            .world_mut()
+            .get_mut::<EguiContext>(self.context)
+            .expect("Egui context must exist")
+            .into_inner()
+            .get_mut()
+    }
+
+    /// Returns the settings of the context.
+    pub fn settings_mut(&mut self) -> bevy_ecs::world::Mut<'_, EguiContextSettings> {
+        self.app
+            .world_mut()
+            .get_mut::<EguiContextSettings>(self.context)
+            .expect("Egui context must exist")
+    }
+
+    /// Returns the Egui context entity.
+    pub fn context(&self) -> Entity {
+        self.context
+    }
+
+    /// Returns the underlying app.
+    pub fn app(&self) -> &App {
+        &self.app
+    }
+
+    /// Returns the underlying app mutably, e.g. for adding other plugins or resources.
+    pub fn app_mut(&mut self) -> &mut App {
+        &mut self.app
+    }
+
+    fn offscreen_context_mut(&mut self) -> bevy_ecs::world::Mut<'_, EguiOffscreenContext> {
+        self.app
+            .world_mut()
+            .get_mut::<EguiOffscreenContext>(self.context)
+            .expect("Egui context must exist")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EguiTestApp;
+    use crate::EguiContexts;
+    use bevy_ecs::{error::Result, resource::Resource, system::ResMut};
+
+    #[derive(Resource, Default)]
+    struct Button {
+        rect: Option<egui::Rect>,
+        clicks: usize,
+    }
+
+    fn button_ui_system(mut contexts: EguiContexts, mut button: ResMut<Button>) -> Result {
+        egui::CentralPanel::default().show(contexts.ctx_mut()?, |ui| {
+            let response = ui.button("Click me");
+            button.rect = Some(response.rect);
+            if response.clicked() {
+                button.clicks += 1;
+            }
+        });
+        Ok(())
+    }
+
+    fn scroll_ui_system(mut contexts: EguiContexts) -> Result {
+        let ctx = contexts.ctx_mut()?;
+        egui::SidePanel::left("scroll_panel")
+            .exact_width(200.0)
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for i in 0..100 {
+                        ui.label(format!("Item {i}"));
+                    }
+                });
+            });
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.label("Nothing to scroll here");
+        });
+        Ok(())
+    }
+
+    fn scroll_at(app: &mut EguiTestApp, pos: egui::Pos2) {
+        app.push_event(egui::Event::PointerMoved(pos))
+            .push_event(egui::Event::MouseWheel {
+                unit: egui::MouseWheelUnit::Point,
+                delta: egui::vec2(0.0, -4.0),
+                modifiers: egui::Modifiers::NONE,
+            })
+            .update();
+    }
+
+    #[test]
+    fn test_wants_scroll_only_over_scroll_areas() {
+        let mut app = EguiTestApp::new();
+        app.settings_mut().capture_scroll_only_over_scroll_areas = true;
+        app.add_ui_systems(scroll_ui_system);
+        // Lay out the scroll area first, so that it knows its content size.
+        app.update();
+
+        scroll_at(&mut app, egui::pos2(100.0, 300.0));
+        assert!(app.wants_input().wants_scroll());
+
+        scroll_at(&mut app, egui::pos2(500.0, 300.0));
+        assert!(app.wants_input().is_pointer_over_area());
+        assert!(!app.wants_input().wants_scroll());
+
+        // The outcome is kept while the pointer stays still.
+        app.update();
+        assert!(!app.wants_input().wants_scroll());
+    }
+
+    #[test]
+    fn test_click_button() {
+        let mut app = EguiTestApp::new();
+        app.app_mut().init_resource::<Button>();
+        app.add_ui_systems(button_ui_system);
+        // Lay out the button first, so that the pointer events can hit it.
+        app.update();
+
+        let button_pos = app
+            .app()
+            .world()
+            .resource::<Button>()
+            .rect
+            .expect("button must be laid out")
+            .center();
+        app.push_event(egui::Event::PointerMoved(button_pos))
+            .push_event(egui::Event::PointerButton {
+                pos: button_pos,
+                button: egui::PointerButton::Primary,
+                pressed: true,
+                modifiers: egui::Modifiers::NONE,
+            })
+            .update();
+        assert_eq!(app.app().world().resource::<Button>().clicks, 0);
+        assert!(app.wants_input().is_pointer_over_area());
+
+        app.push_event(egui::Event::PointerButton {
+            pos: button_pos,
+            button: egui::PointerButton::Primary,
+            pressed: false,
+            modifiers: egui::Modifiers::NONE,
+        })
+        .update();
+        assert_eq!(app.app().world().resource::<Button>().clicks, 1);
+    }
+}
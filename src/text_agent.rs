@@ -3,11 +3,10 @@
 
 use crate::{
     input::{EguiInputEvent, FocusedNonWindowEguiContext},
-    EguiContextSettings, EguiInput, EguiOutput, EventClosure, SubscribedEvents,
+    EguiContextSettings, EguiInput, EguiOutput, EventClosure, PrimaryEguiContext, SubscribedEvents,
 };
 use bevy_ecs::prelude::*;
 use bevy_log as log;
-use bevy_platform::collections::HashSet;
 use bevy_window::RequestRedraw;
 use crossbeam_channel::{unbounded, Receiver, Sender};
 use std::sync::{LazyLock, Mutex};
@@ -79,44 +78,87 @@ pub fn process_safari_virtual_keyboard_system(
 }
 
 /// Listens to the [`TextAgentChannel`] channel and wraps messages into [`EguiInputEvent`] events.
+///
+/// As there's only one text agent on a page, its events are routed to a single context, which is
+/// (in the order of priority):
+/// - the context stored in [`FocusedNonWindowEguiContext`],
+/// - a focused context that is editing text (has a focused text edit widget),
+/// - a focused context, preferring the one with the [`PrimaryEguiContext`] component,
+/// - the context with the [`PrimaryEguiContext`] component.
 pub fn write_text_agent_channel_events_system(
     channel: Res<TextAgentChannel>,
     focused_non_window_egui_context: Option<Res<FocusedNonWindowEguiContext>>,
-    egui_contexts: Query<(Entity, &EguiContextSettings)>,
+    egui_contexts: Query<(
+        Entity,
+        &EguiContextSettings,
+        &EguiInput,
+        &EguiOutput,
+        Has<PrimaryEguiContext>,
+    )>,
     mut egui_input_event_writer: EventWriter<EguiInputEvent>,
     mut redraw_event: EventWriter<RequestRedraw>,
-) -> Result {
-    let mut processed_entities = HashSet::new();
-
-    for (context_entity, _context_settings) in egui_contexts {
-        let mut redraw = false;
-        let context = focused_non_window_egui_context
-            .as_deref()
-            .map_or(context_entity, |context| context.0);
-
-        if !processed_entities.insert(context) {
+) {
+    let context = text_agent_context(focused_non_window_egui_context.as_deref(), &egui_contexts);
+    let context = context.filter(|context| {
+        egui_contexts
+            .get(*context)
+            .is_ok_and(|(_, context_settings, ..)| {
+                context_settings.input_enabled
+                    && context_settings
+                        .input_system_settings
+                        .run_write_text_agent_channel_events_system
+            })
+    });
+
+    // The channel is drained even if there's no context to receive the events,
+    // to avoid replaying stale input once a context gets focused.
+    let mut redraw = false;
+    while let Ok(event) = channel.receiver.try_recv() {
+        let Some(context) = context else {
             continue;
-        }
+        };
+        redraw = true;
+        egui_input_event_writer.write(EguiInputEvent { context, event });
+    }
+    if redraw {
+        redraw_event.write(RequestRedraw);
+    }
+}
 
-        let (_, context_settings) = egui_contexts.get(context)?;
+fn text_agent_context(
+    focused_non_window_egui_context: Option<&FocusedNonWindowEguiContext>,
+    egui_contexts: &Query<(
+        Entity,
+        &EguiContextSettings,
+        &EguiInput,
+        &EguiOutput,
+        Has<PrimaryEguiContext>,
+    )>,
+) -> Option<Entity> {
+    if let Some(FocusedNonWindowEguiContext(context)) = focused_non_window_egui_context {
+        if egui_contexts.contains(*context) {
+            return Some(*context);
+        }
+    }
 
-        if !context_settings
-            .input_system_settings
-            .run_write_text_agent_channel_events_system
-        {
+    let mut focused_context = None;
+    let mut primary_context = None;
+    for (context, _, egui_input, egui_output, is_primary) in egui_contexts {
+        if is_primary {
+            primary_context = Some(context);
+        }
+        if !egui_input.focused {
             continue;
         }
-
-        while let Ok(event) = channel.receiver.try_recv() {
-            redraw = true;
-            egui_input_event_writer.write(EguiInputEvent { context, event });
+        let platform_output = &egui_output.platform_output;
+        if platform_output.ime.is_some() || platform_output.mutable_text_under_cursor {
+            return Some(context);
         }
-        if redraw {
-            redraw_event.write(RequestRedraw);
+        if focused_context.is_none() || is_primary {
+            focused_context = Some(context);
         }
     }
-
-    Ok(())
+    focused_context.or(primary_context)
 }
 
 /// Installs a text agent on startup.
@@ -1,7 +1,7 @@
 use crate::{
     input::{EguiInputEvent, FocusedNonWindowEguiContext},
-    string_from_js_value, EguiClipboard, EguiContext, EguiContextSettings, EventClosure,
-    SubscribedEvents,
+    string_from_js_value, EguiClipboard, EguiClipboardContentKind, EguiContext,
+    EguiContextClipboard, EguiContextSettings, EventClosure, SubscribedEvents,
 };
 use bevy_ecs::prelude::*;
 use bevy_log as log;
@@ -25,7 +25,10 @@ pub fn startup_setup_web_events_system(
 /// Receives web clipboard events and wraps them as [`EguiInputEvent`] events.
 pub fn write_web_clipboard_events_system(
     focused_non_window_egui_context: Option<Res<FocusedNonWindowEguiContext>>,
-    egui_contexts: Query<(Entity, &EguiContextSettings), With<EguiContext>>,
+    egui_contexts: Query<
+        (Entity, &EguiContextSettings, Has<EguiContextClipboard>),
+        With<EguiContext>,
+    >,
     mut egui_clipboard: ResMut<EguiClipboard>,
     mut egui_input_event_writer: EventWriter<EguiInputEvent>,
 ) -> Result {
@@ -40,7 +43,7 @@ pub fn write_web_clipboard_events_system(
         return Ok(());
     }
 
-    for (context_entity, _context_settings) in egui_contexts {
+    for (context_entity, ..) in &egui_contexts {
         let context = focused_non_window_egui_context
             .as_deref()
             .map_or(context_entity, |context| context.0);
@@ -49,10 +52,14 @@ pub fn write_web_clipboard_events_system(
             continue;
         }
 
-        let (_, context_settings) = egui_contexts.get(context_entity)?;
-        if !context_settings
-            .input_system_settings
-            .run_write_web_clipboard_events_system
+        let (_, context_settings, has_context_clipboard) = egui_contexts.get(context)?;
+        // Contexts with their own clipboard handle the shortcuts in `write_keyboard_input_events_system`,
+        // browser events would duplicate them and leak the OS clipboard contents into the context.
+        if has_context_clipboard
+            || !context_settings.input_enabled
+            || !context_settings
+                .input_system_settings
+                .run_write_web_clipboard_events_system
         {
             continue;
         }
@@ -72,12 +79,20 @@ pub fn write_web_clipboard_events_system(
                     });
                 }
                 crate::web_clipboard::WebClipboardEvent::Paste(text) => {
+                    egui_clipboard.set_last_event_kind(if text.is_empty() {
+                        EguiClipboardContentKind::Empty
+                    } else {
+                        EguiClipboardContentKind::Text
+                    });
                     egui_clipboard.set_text_internal(&text);
                     egui_input_event_writer.write(EguiInputEvent {
                         context,
                         event: egui::Event::Paste(text),
                     });
                 }
+                crate::web_clipboard::WebClipboardEvent::PasteImage => {
+                    egui_clipboard.set_last_event_kind(EguiClipboardContentKind::Image);
+                }
             }
         }
     }
@@ -90,6 +105,7 @@ pub fn write_web_clipboard_events_system(
 pub struct WebClipboard {
     event_receiver: Option<Receiver<WebClipboardEvent>>,
     contents: Option<String>,
+    last_event_kind: Option<EguiClipboardContentKind>,
 }
 
 /// Events sent by the `cut`/`copy`/`paste` listeners.
@@ -101,6 +117,8 @@ pub enum WebClipboardEvent {
     Copy,
     /// Is sent whenever the `paste` event listener is called, includes the plain text content.
     Paste(String),
+    /// Is sent whenever the `paste` event listener is called with an image and no text.
+    PasteImage,
 }
 
 impl WebClipboard {
@@ -122,6 +140,16 @@ impl WebClipboard {
         self.contents.clone()
     }
 
+    /// Returns the kind of the contents of the last `paste` event.
+    pub fn last_event_kind(&self) -> Option<EguiClipboardContentKind> {
+        self.last_event_kind
+    }
+
+    /// Sets the kind of the contents of the last `paste` event.
+    pub fn set_last_event_kind(&mut self, kind: EguiClipboardContentKind) {
+        self.last_event_kind = Some(kind);
+    }
+
     /// Places the image onto the clipboard.
     pub fn set_image(&mut self, image: &egui::ColorImage) {
         self.contents = None;
@@ -239,7 +267,17 @@ fn setup_clipboard_paste(subscribed_events: &mut SubscribedEvents, tx: Sender<We
         };
         match clipboard_data.get_data("text/plain") {
             Ok(data) => {
-                if tx.send(WebClipboardEvent::Paste(data)).is_err() {
+                let has_image = clipboard_data.types().iter().any(|data_type| {
+                    data_type
+                        .as_string()
+                        .is_some_and(|data_type| data_type.starts_with("image/"))
+                });
+                let event = if data.is_empty() && has_image {
+                    WebClipboardEvent::PasteImage
+                } else {
+                    WebClipboardEvent::Paste(data)
+                };
+                if tx.send(event).is_err() {
                     log::error!("Failed to send the \"paste\" event: channel is disconnected");
                 }
             }